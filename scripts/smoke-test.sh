@@ -251,6 +251,23 @@ assert_contains "$tools_list_body" '"list_logs"' "tools/list did not include lis
 assert_contains "$tools_list_body" '"get_unit_status"' "tools/list did not include get_unit_status"
 assert_contains "$tools_list_body" '"get_container_status"' "tools/list did not include get_container_status"
 assert_contains "$tools_list_body" '"get_pod_status"' "tools/list did not include get_pod_status"
+assert_contains "$tools_list_body" '"list_units"' "tools/list did not include list_units"
+assert_contains "$tools_list_body" '"get_unit_dependencies"' "tools/list did not include get_unit_dependencies"
+assert_contains "$tools_list_body" '"get_unit_file"' "tools/list did not include get_unit_file"
+assert_contains "$tools_list_body" '"get_unit_resources"' "tools/list did not include get_unit_resources"
+assert_contains "$tools_list_body" '"top_units"' "tools/list did not include top_units"
+assert_contains "$tools_list_body" '"analyze_unit_security"' "tools/list did not include analyze_unit_security"
+assert_contains "$tools_list_body" '"get_boot_timeline"' "tools/list did not include get_boot_timeline"
+assert_contains "$tools_list_body" '"list_boots"' "tools/list did not include list_boots"
+assert_contains "$tools_list_body" '"list_sessions"' "tools/list did not include list_sessions"
+assert_contains "$tools_list_body" '"list_machines"' "tools/list did not include list_machines"
+assert_contains "$tools_list_body" '"list_jobs"' "tools/list did not include list_jobs"
+assert_contains "$tools_list_body" '"explain_timer"' "tools/list did not include explain_timer"
+assert_contains "$tools_list_body" '"get_timer_history"' "tools/list did not include get_timer_history"
+assert_contains "$tools_list_body" '"list_coredumps"' "tools/list did not include list_coredumps"
+assert_contains "$tools_list_body" '"diagnose_unit"' "tools/list did not include diagnose_unit"
+assert_contains "$tools_list_body" '"get_host_overview"' "tools/list did not include get_host_overview"
+assert_contains "$tools_list_body" '"get_storage_status"' "tools/list did not include get_storage_status"
 assert_contains "$tools_list_body" 'state accepts active' "tools/list list_services guidance did not mention valid states"
 assert_contains "$tools_list_body" 'sort accepts next, last, name, or state' "tools/list list_timers guidance did not mention valid sort values"
 assert_contains "$tools_list_body" 'order accepts asc or desc' "tools/list list_timers guidance did not mention valid order values"
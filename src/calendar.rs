@@ -0,0 +1,633 @@
+//! systemd `OnCalendar=` expression parsing and elapse prediction.
+//!
+//! Follows the calendar event grammar of `systemd.time(7)`: optional weekdays,
+//! a date, a time, and a time zone, each component accepting `*`, values,
+//! `..` ranges, `/` repetitions, and `,` lists, plus the shorthand names.
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, Timelike, Utc};
+
+use crate::time_zone::TimeZone;
+
+const MIN_YEAR: u32 = 1970;
+const MAX_YEAR: u32 = 2199;
+const USEC_PER_SECOND: u32 = 1_000_000;
+const MAX_SECOND_USEC: u32 = 60 * USEC_PER_SECOND - 1;
+const MAX_RESOLVE_ATTEMPTS: usize = 1000;
+
+const WEEKDAY_NAMES: [(&str, &str); 7] = [
+    ("mon", "monday"),
+    ("tue", "tuesday"),
+    ("wed", "wednesday"),
+    ("thu", "thursday"),
+    ("fri", "friday"),
+    ("sat", "saturday"),
+    ("sun", "sunday"),
+];
+
+const SHORTHANDS: [(&str, &str); 10] = [
+    ("minutely", "*-*-* *:*:00"),
+    ("hourly", "*-*-* *:00:00"),
+    ("daily", "*-*-* 00:00:00"),
+    ("weekly", "Mon *-*-* 00:00:00"),
+    ("monthly", "*-*-01 00:00:00"),
+    ("quarterly", "*-01,04,07,10-01 00:00:00"),
+    ("semiannually", "*-01,07-01 00:00:00"),
+    ("semi-annually", "*-01,07-01 00:00:00"),
+    ("yearly", "*-01-01 00:00:00"),
+    ("annually", "*-01-01 00:00:00"),
+];
+
+/// One parsed calendar event.
+///
+/// Seconds are kept in microseconds so fractional values like `05.5` match
+/// exactly. Without a time zone the event follows the manager's local time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalendarSpec {
+    weekdays: u8,
+    year: Component,
+    month: Component,
+    day: Component,
+    end_of_month: bool,
+    hour: Component,
+    minute: Component,
+    second: Component,
+    time_zone: Option<TimeZone>,
+}
+
+/// Comma-separated alternatives of one field; empty matches every value.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Component(Vec<ComponentRange>);
+
+/// `start`, `start..stop`, `start/repeat`, or `start..stop/repeat`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ComponentRange {
+    start: u32,
+    stop: Option<u32>,
+    repeat: Option<u32>,
+}
+
+impl CalendarSpec {
+    /// Parses one calendar expression such as `Mon..Fri *-*-* 09:00` or `daily UTC`.
+    ///
+    /// `time_zone` is the caller's resolution of [`time_zone_name`]; parsing
+    /// never reads the zoneinfo database, and an expression naming a zone that
+    /// did not resolve is rejected. Errors name the offending part; `@` epoch
+    /// timestamps are not supported.
+    pub fn parse(expression: &str, time_zone: Option<TimeZone>) -> Result<Self, &'static str> {
+        let mut tokens = expression.split_whitespace().collect::<Vec<_>>();
+        if tokens.is_empty() {
+            return Err("empty calendar expression");
+        }
+        let time_zone = match time_zone_name(expression) {
+            Some(_) => {
+                tokens.pop();
+                Some(time_zone.ok_or("unknown time zone")?)
+            }
+            None => None,
+        };
+        if let [name] = tokens.as_slice()
+            && let Some((_, expansion)) = SHORTHANDS
+                .iter()
+                .find(|(shorthand, _)| name.eq_ignore_ascii_case(shorthand))
+        {
+            tokens = expansion.split_whitespace().collect();
+        }
+
+        let mut weekdays = 0x7f;
+        if let Some(first) = tokens.first()
+            && first.starts_with(|c: char| c.is_ascii_alphabetic())
+        {
+            weekdays = parse_weekdays(first)?;
+            tokens.remove(0);
+        }
+        let (mut date, mut time) = (None, None);
+        for token in tokens {
+            let slot = if token.contains(':') {
+                &mut time
+            } else {
+                &mut date
+            };
+            if slot.replace(token).is_some() {
+                return Err("calendar expression has extra components");
+            }
+        }
+
+        let (year, month, day, end_of_month) = match date {
+            Some(date) => parse_date(date)?,
+            None => (
+                Component::default(),
+                Component::default(),
+                Component::default(),
+                false,
+            ),
+        };
+        let (hour, minute, second) = match time {
+            Some(time) => parse_time(time)?,
+            None => (
+                Component::value(0),
+                Component::value(0),
+                Component::value(0),
+            ),
+        };
+        Ok(Self {
+            weekdays,
+            year,
+            month,
+            day,
+            end_of_month,
+            hour,
+            minute,
+            second,
+            time_zone,
+        })
+    }
+
+    /// Returns the first elapse strictly after `after`.
+    ///
+    /// Expressions without a time zone use `local_zone`. Local times skipped by
+    /// a daylight saving change elapse right after the gap; repeated ones elapse
+    /// at their first occurrence. `None` means no elapse before year 2200.
+    pub fn next_after(&self, after: DateTime<Utc>, local_zone: &TimeZone) -> Option<DateTime<Utc>> {
+        let zone = self.time_zone.as_ref().unwrap_or(local_zone);
+        let mut from = zone.to_local(after).naive_local() + Duration::microseconds(1);
+        for _ in 0..MAX_RESOLVE_ATTEMPTS {
+            let candidate = self.next_local(from)?;
+            let (earliest, latest) = zone.resolve_local(candidate);
+            if earliest > after {
+                return Some(earliest);
+            }
+            if latest > after {
+                return Some(latest);
+            }
+            from = candidate + Duration::microseconds(1);
+        }
+        None
+    }
+
+    /// Returns the first matching wall-clock time at or after `from`.
+    fn next_local(&self, from: NaiveDateTime) -> Option<NaiveDateTime> {
+        let mut year = u32::try_from(from.year()).ok()?;
+        let (mut month, mut day) = (from.month(), from.day());
+        let (mut hour, mut minute) = (from.hour(), from.minute());
+        let mut second = from.second() * USEC_PER_SECOND + from.nanosecond() / 1000;
+        loop {
+            let next_year = self.year.next_match(year.max(MIN_YEAR), MAX_YEAR)?;
+            if next_year != year {
+                (year, month, day, hour, minute, second) = (next_year, 1, 1, 0, 0, 0);
+            }
+            let Some(next_month) = self.month.next_match(month, 12) else {
+                (year, month, day, hour, minute, second) = (year + 1, 1, 1, 0, 0, 0);
+                continue;
+            };
+            if next_month != month {
+                (month, day, hour, minute, second) = (next_month, 1, 0, 0, 0);
+            }
+            let days_in_month = days_in_month(year, month)?;
+            let Some(next_day) = (day..=days_in_month)
+                .find(|day| self.day_matches(year, month, *day, days_in_month))
+            else {
+                (month, day, hour, minute, second) = (month + 1, 1, 0, 0, 0);
+                continue;
+            };
+            if next_day != day {
+                (day, hour, minute, second) = (next_day, 0, 0, 0);
+            }
+            let Some(next_hour) = self.hour.next_match(hour, 23) else {
+                (day, hour, minute, second) = (day + 1, 0, 0, 0);
+                continue;
+            };
+            if next_hour != hour {
+                (hour, minute, second) = (next_hour, 0, 0);
+            }
+            let Some(next_minute) = self.minute.next_match(minute, 59) else {
+                (hour, minute, second) = (hour + 1, 0, 0);
+                continue;
+            };
+            if next_minute != minute {
+                (minute, second) = (next_minute, 0);
+            }
+            let Some(next_second) = self.second.next_match(second, MAX_SECOND_USEC) else {
+                (minute, second) = (minute + 1, 0);
+                continue;
+            };
+            return NaiveDate::from_ymd_opt(i32::try_from(year).ok()?, month, day)?
+                .and_hms_micro_opt(
+                    hour,
+                    minute,
+                    next_second / USEC_PER_SECOND,
+                    next_second % USEC_PER_SECOND,
+                );
+        }
+    }
+
+    /// Checks the day component, counted from the month end after `~`, and weekdays.
+    fn day_matches(&self, year: u32, month: u32, day: u32, days_in_month: u32) -> bool {
+        let value = if self.end_of_month {
+            days_in_month + 1 - day
+        } else {
+            day
+        };
+        self.day.next_match(value, 31) == Some(value)
+            && NaiveDate::from_ymd_opt(year as i32, month, day).is_some_and(|date| {
+                self.weekdays & (1 << date.weekday().num_days_from_monday()) != 0
+            })
+    }
+}
+
+/// Returns the time zone named at the end of `expression`, such as `UTC` or
+/// `Europe/Berlin`.
+pub fn time_zone_name(expression: &str) -> Option<&str> {
+    expression.split_whitespace().last().filter(|last| {
+        last.starts_with(|c: char| c.is_ascii_alphabetic())
+            && !is_weekday_list(last)
+            && !is_shorthand(last)
+    })
+}
+
+/// Merges the elapses of several calendar events in time order.
+///
+/// Returns up to `count` elapses after `after` and no later than `until`, each
+/// with the index of the first event producing it; coinciding elapses of
+/// several events count once, as the timer fires once.
+pub fn merged_elapses(
+    specs: &[CalendarSpec],
+    after: DateTime<Utc>,
+    until: Option<DateTime<Utc>>,
+    count: usize,
+    local_zone: &TimeZone,
+) -> Vec<(DateTime<Utc>, usize)> {
+    let mut next = specs
+        .iter()
+        .map(|spec| spec.next_after(after, local_zone))
+        .collect::<Vec<_>>();
+    let mut elapses = Vec::new();
+    while elapses.len() < count {
+        let Some((index, at)) = next
+            .iter()
+            .enumerate()
+            .filter_map(|(index, at)| at.map(|at| (index, at)))
+            .min_by_key(|(index, at)| (*at, *index))
+        else {
+            break;
+        };
+        if until.is_some_and(|until| at > until) {
+            break;
+        }
+        elapses.push((at, index));
+        for (spec, slot) in specs.iter().zip(next.iter_mut()) {
+            if *slot == Some(at) {
+                *slot = spec.next_after(at, local_zone);
+            }
+        }
+    }
+    elapses
+}
+
+impl Component {
+    fn value(value: u32) -> Self {
+        Self(vec![ComponentRange {
+            start: value,
+            stop: None,
+            repeat: None,
+        }])
+    }
+
+    /// Returns the smallest matching value in `value..=max`.
+    fn next_match(&self, value: u32, max: u32) -> Option<u32> {
+        if value > max {
+            return None;
+        }
+        if self.0.is_empty() {
+            return Some(value);
+        }
+        self.0
+            .iter()
+            .filter_map(|range| {
+                let stop = match (range.stop, range.repeat) {
+                    (Some(stop), _) => stop,
+                    (None, Some(_)) => max,
+                    (None, None) => range.start,
+                };
+                let step = range.repeat.unwrap_or(1);
+                let candidate = if value <= range.start {
+                    range.start
+                } else {
+                    let steps = (value - range.start).div_ceil(step);
+                    range.start.checked_add(steps.checked_mul(step)?)?
+                };
+                (candidate <= stop.min(max)).then_some(candidate)
+            })
+            .min()
+    }
+}
+
+fn is_shorthand(token: &str) -> bool {
+    SHORTHANDS
+        .iter()
+        .any(|(shorthand, _)| token.eq_ignore_ascii_case(shorthand))
+}
+
+fn weekday_index(name: &str) -> Option<u32> {
+    WEEKDAY_NAMES
+        .iter()
+        .position(|(short, long)| {
+            name.eq_ignore_ascii_case(short) || name.eq_ignore_ascii_case(long)
+        })
+        .map(|index| index as u32)
+}
+
+fn is_weekday_list(token: &str) -> bool {
+    parse_weekdays(token).is_ok()
+}
+
+/// Parses `Mon,Wed..Fri` into a Monday-first bit set.
+fn parse_weekdays(token: &str) -> Result<u8, &'static str> {
+    let mut weekdays = 0;
+    for item in token.split(',') {
+        let (start, stop) = item.split_once("..").unwrap_or((item, item));
+        let start = weekday_index(start).ok_or("invalid weekday")?;
+        let stop = weekday_index(stop).ok_or("invalid weekday")?;
+        if start > stop {
+            return Err("invalid weekday range");
+        }
+        for index in start..=stop {
+            weekdays |= 1 << index;
+        }
+    }
+    Ok(weekdays)
+}
+
+/// Parses `[year-]month-day` or `[year-]month~day` (days counted from the month end).
+fn parse_date(token: &str) -> Result<(Component, Component, Component, bool), &'static str> {
+    let (head, day, end_of_month) = match token.rsplit_once('~') {
+        Some((head, day)) => (head, day, true),
+        None => {
+            let (head, day) = token.rsplit_once('-').ok_or("invalid date")?;
+            (head, day, false)
+        }
+    };
+    let (year, month) = match head.split('-').collect::<Vec<_>>().as_slice() {
+        [month] => (Component::default(), *month),
+        [year, month] => (parse_component(year, MIN_YEAR, MAX_YEAR, false)?, *month),
+        _ => return Err("invalid date"),
+    };
+    Ok((
+        year,
+        parse_component(month, 1, 12, false)?,
+        parse_component(day, 1, 31, false)?,
+        end_of_month,
+    ))
+}
+
+/// Parses `hour:minute[:second]`; omitted seconds are `00`.
+fn parse_time(token: &str) -> Result<(Component, Component, Component), &'static str> {
+    let parts = token.split(':').collect::<Vec<_>>();
+    let (hour, minute, second) = match parts.as_slice() {
+        [hour, minute] => (*hour, *minute, "00"),
+        [hour, minute, second] => (*hour, *minute, *second),
+        _ => return Err("invalid time"),
+    };
+    Ok((
+        parse_component(hour, 0, 23, false)?,
+        parse_component(minute, 0, 59, false)?,
+        parse_component(second, 0, MAX_SECOND_USEC, true)?,
+    ))
+}
+
+/// Parses one comma-separated component; `seconds` values are decimal microseconds.
+fn parse_component(
+    token: &str,
+    min: u32,
+    max: u32,
+    seconds: bool,
+) -> Result<Component, &'static str> {
+    let value = |text: &str| -> Result<u32, &'static str> {
+        let parsed = if seconds {
+            parse_decimal_usec(text)
+        } else if text.bytes().all(|byte| byte.is_ascii_digit()) {
+            text.parse().ok()
+        } else {
+            None
+        };
+        parsed
+            .filter(|value| (min..=max).contains(value))
+            .ok_or("calendar value out of range")
+    };
+    let mut ranges = Vec::new();
+    for item in token.split(',') {
+        let (body, repeat) = match item.split_once('/') {
+            Some((body, repeat)) => {
+                let repeat = if seconds {
+                    parse_decimal_usec(repeat)
+                } else {
+                    repeat.parse().ok()
+                };
+                (
+                    body,
+                    Some(
+                        repeat
+                            .filter(|value| *value > 0)
+                            .ok_or("invalid repetition")?,
+                    ),
+                )
+            }
+            None => (item, None),
+        };
+        if body == "*" {
+            if repeat.is_none() {
+                return Ok(Component::default());
+            }
+            ranges.push(ComponentRange {
+                start: min,
+                stop: None,
+                repeat,
+            });
+            continue;
+        }
+        let (start, stop) = match body.split_once("..") {
+            Some((start, stop)) => (value(start)?, Some(value(stop)?)),
+            None => (value(body)?, None),
+        };
+        if stop.is_some_and(|stop| stop < start) {
+            return Err("invalid calendar range");
+        }
+        ranges.push(ComponentRange {
+            start,
+            stop,
+            repeat,
+        });
+    }
+    Ok(Component(ranges))
+}
+
+/// Parses `SS[.ffffff]` into microseconds.
+fn parse_decimal_usec(text: &str) -> Option<u32> {
+    let (whole, fraction) = text.split_once('.').unwrap_or((text, ""));
+    if whole.is_empty()
+        || fraction.len() > 6
+        || !whole
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|byte| byte.is_ascii_digit())
+    {
+        return None;
+    }
+    let whole: u32 = whole.parse().ok()?;
+    let fraction: u32 = format!("{fraction:0<6}").parse().ok()?;
+    whole.checked_mul(USEC_PER_SECOND)?.checked_add(fraction)
+}
+
+fn days_in_month(year: u32, month: u32) -> Option<u32> {
+    let year = i32::try_from(year).ok()?;
+    let next = if month == 12 {
+        NaiveDate::from_ymd_opt(year + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(year, month + 1, 1)
+    }?;
+    Some(next.pred_opt()?.day())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CalendarSpec, merged_elapses, time_zone_name};
+    use crate::time_zone::TimeZone;
+    use chrono::{DateTime, Utc};
+
+    /// Parses `expression`, resolving only `UTC` as its time zone.
+    fn parse(expression: &str) -> Result<CalendarSpec, &'static str> {
+        let time_zone = time_zone_name(expression)
+            .filter(|name| name.eq_ignore_ascii_case("UTC"))
+            .map(|_| TimeZone::utc());
+        CalendarSpec::parse(expression, time_zone)
+    }
+
+    fn utc(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .expect("timestamp")
+            .with_timezone(&Utc)
+    }
+
+    fn next(expression: &str, after: &str) -> Option<String> {
+        parse(expression)
+            .expect("calendar expression")
+            .next_after(utc(after), &TimeZone::utc())
+            .map(|at| at.to_rfc3339())
+    }
+
+    #[test]
+    fn predicts_shorthands_and_normalized_expressions() {
+        assert_eq!(
+            next("daily", "2026-02-27T10:00:00Z").as_deref(),
+            Some("2026-02-28T00:00:00+00:00")
+        );
+        assert_eq!(
+            next("weekly", "2026-02-27T10:00:00Z").as_deref(),
+            Some("2026-03-02T00:00:00+00:00")
+        );
+        assert_eq!(
+            next("*-*-* *:00/15:00", "2026-02-27T10:07:00Z").as_deref(),
+            Some("2026-02-27T10:15:00+00:00")
+        );
+        assert_eq!(
+            next("Mon..Fri *-*-* 09:00:00", "2026-02-27T09:00:00Z").as_deref(),
+            Some("2026-03-02T09:00:00+00:00")
+        );
+        assert_eq!(
+            next("quarterly", "2026-02-27T00:00:00Z").as_deref(),
+            Some("2026-04-01T00:00:00+00:00")
+        );
+        assert_eq!(
+            next("*-02-29 12:00", "2026-01-01T00:00:00Z").as_deref(),
+            Some("2028-02-29T12:00:00+00:00")
+        );
+        assert_eq!(
+            next("*-*~01 23:30", "2026-02-01T00:00:00Z").as_deref(),
+            Some("2026-02-28T23:30:00+00:00")
+        );
+        assert_eq!(
+            next("2026-01..03-01/7 06:00:00.5", "2026-02-10T00:00:00Z").as_deref(),
+            Some("2026-02-15T06:00:00.500+00:00")
+        );
+        assert_eq!(next("2020-01-01", "2026-01-01T00:00:00Z"), None);
+        assert_eq!(next("*-02-30", "2026-01-01T00:00:00Z"), None);
+    }
+
+    #[test]
+    fn applies_time_zones_across_daylight_saving_changes() {
+        let berlin =
+            TimeZone::from_posix("Europe/Berlin", "CET-1CEST,M3.5.0,M10.5.0/3").expect("rule");
+        let spec = parse("*-*-* 02:30:00").expect("calendar expression");
+        let at = |after: &str| {
+            spec.next_after(utc(after), &berlin)
+                .map(|at| at.to_rfc3339())
+        };
+        assert_eq!(
+            at("2026-03-28T12:00:00Z").as_deref(),
+            Some("2026-03-29T01:30:00+00:00")
+        );
+        assert_eq!(
+            at("2026-10-24T12:00:00Z").as_deref(),
+            Some("2026-10-25T00:30:00+00:00")
+        );
+        assert_eq!(
+            at("2026-10-25T00:30:00Z").as_deref(),
+            Some("2026-10-26T01:30:00+00:00")
+        );
+
+        let utc_spec = parse("daily UTC").expect("calendar expression");
+        assert_eq!(
+            utc_spec
+                .next_after(utc("2026-07-01T12:00:00Z"), &berlin)
+                .map(|at| at.to_rfc3339())
+                .as_deref(),
+            Some("2026-07-02T00:00:00+00:00")
+        );
+    }
+
+    #[test]
+    fn merges_elapses_of_several_events_once() {
+        let specs = ["*-*-* 06:00:00", "*-*-* 06,18:00:00"]
+            .map(|expression| parse(expression).expect("calendar expression"));
+        let elapses = merged_elapses(
+            &specs,
+            utc("2026-02-27T00:00:00Z"),
+            Some(utc("2026-02-28T12:00:00Z")),
+            10,
+            &TimeZone::utc(),
+        );
+        assert_eq!(
+            elapses
+                .iter()
+                .map(|(at, index)| (at.to_rfc3339(), *index))
+                .collect::<Vec<_>>(),
+            vec![
+                ("2026-02-27T06:00:00+00:00".to_string(), 0),
+                ("2026-02-27T18:00:00+00:00".to_string(), 1),
+                ("2026-02-28T06:00:00+00:00".to_string(), 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_expressions() {
+        for expression in [
+            "",
+            "@1700000000",
+            "*-13-01",
+            "*-*-* 24:00",
+            "Fri..Mon",
+            "*-*-* 10:00 10:00",
+            "*-*-* *:*/0",
+            "*-*-* 10:00 Mars/Olympus",
+            "*-*-* 1:2:3:4",
+        ] {
+            assert!(parse(expression).is_err(), "{expression} must be rejected");
+        }
+        assert_eq!(
+            time_zone_name("*-*-* 10:00 Europe/Berlin"),
+            Some("Europe/Berlin")
+        );
+        assert_eq!(time_zone_name("Mon,Fri"), None);
+        assert_eq!(time_zone_name("daily"), None);
+    }
+}
@@ -5,6 +5,7 @@ use thiserror::Error;
 use crate::rate_limit::{
     DEFAULT_BURST, DEFAULT_REQUESTS_PER_SECOND, MAX_BURST, MAX_REQUESTS_PER_SECOND, RateLimitPolicy,
 };
+use crate::systemd_client::{MachineBuses, MachineName};
 
 const MIN_API_TOKEN_LENGTH: usize = 16;
 
@@ -15,6 +16,9 @@ struct RawConfig {
     bind_port: Option<String>,
     rate_limit_requests_per_second: Option<String>,
     rate_limit_burst: Option<String>,
+    unit_cache_enabled: Option<String>,
+    user_scope_uids: Option<String>,
+    machine_scopes: Option<String>,
 }
 
 impl RawConfig {
@@ -28,6 +32,9 @@ impl RawConfig {
             bind_port: env::var("BIND_PORT").ok(),
             rate_limit_requests_per_second: env::var("RATE_LIMIT_REQUESTS_PER_SECOND").ok(),
             rate_limit_burst: env::var("RATE_LIMIT_BURST").ok(),
+            unit_cache_enabled: env::var("UNIT_CACHE_ENABLED").ok(),
+            user_scope_uids: env::var("USER_SCOPE_UIDS").ok(),
+            machine_scopes: env::var("MACHINE_SCOPES").ok(),
         }
     }
 }
@@ -39,6 +46,9 @@ pub struct Config {
     pub bind_port: u16,
     pub rate_limit_requests_per_second: u32,
     pub rate_limit_burst: u32,
+    pub unit_cache_enabled: bool,
+    pub user_scope_uids: Vec<u32>,
+    pub machine_buses: MachineBuses,
 }
 
 #[derive(Clone, Copy, Debug, Error)]
@@ -57,13 +67,21 @@ pub enum ConfigError {
     InvalidRateLimitRequestsPerSecond,
     #[error("RATE_LIMIT_BURST must be an integer between 1 and {MAX_BURST}")]
     InvalidRateLimitBurst,
+    #[error("UNIT_CACHE_ENABLED must be true or false")]
+    InvalidUnitCacheEnabled,
+    #[error("USER_SCOPE_UIDS must be a comma-separated list of numeric UIDs")]
+    InvalidUserScopeUids,
+    #[error(
+        "MACHINE_SCOPES must be a comma-separated list of unique machine names, each optionally followed by =<dbus address>"
+    )]
+    InvalidMachineScopes,
 }
 
 impl Config {
     /// Builds validated runtime config from environment variables.
     ///
-    /// Applies defaults for optional bind and rate-limit settings, and validates
-    /// token length plus all numeric bounds.
+    /// Applies defaults for optional bind, rate-limit, cache, user-scope, and
+    /// machine-scope settings, and validates token length plus all numeric bounds.
     pub fn from_env() -> Result<Self, ConfigError> {
         Self::parse(RawConfig::from_env())
     }
@@ -112,6 +130,12 @@ impl Config {
             MAX_BURST,
             ConfigError::InvalidRateLimitBurst,
         )?;
+        let unit_cache_enabled = parse_bool(
+            raw.unit_cache_enabled.as_deref(),
+            ConfigError::InvalidUnitCacheEnabled,
+        )?;
+        let user_scope_uids = parse_uid_list(raw.user_scope_uids.as_deref())?;
+        let machine_buses = parse_machine_scopes(raw.machine_scopes.as_deref())?;
 
         let config = Self {
             api_token,
@@ -119,6 +143,9 @@ impl Config {
             bind_port,
             rate_limit_requests_per_second,
             rate_limit_burst,
+            unit_cache_enabled,
+            user_scope_uids,
+            machine_buses,
         };
 
         let _ = config.bind_socket()?;
@@ -164,6 +191,72 @@ fn parse_bounded_u32(
     Ok(value)
 }
 
+/// Parses an optional `true`/`false` (or `1`/`0`) flag, defaulting to disabled.
+fn parse_bool(raw: Option<&str>, error: ConfigError) -> Result<bool, ConfigError> {
+    match raw
+        .map(|value| value.trim().to_ascii_lowercase())
+        .as_deref()
+    {
+        None | Some("false" | "0") => Ok(false),
+        Some("true" | "1") => Ok(true),
+        Some(_) => Err(error),
+    }
+}
+
+/// Parses the optional comma-separated UID allowlist for per-user scopes.
+///
+/// Absent or blank values allow no users. UIDs are returned sorted and
+/// deduplicated; empty items and non-numeric or overflowing values are rejected.
+fn parse_uid_list(raw: Option<&str>) -> Result<Vec<u32>, ConfigError> {
+    let Some(raw) = raw.map(str::trim).filter(|value| !value.is_empty()) else {
+        return Ok(Vec::new());
+    };
+    let mut uids = raw
+        .split(',')
+        .map(|item| {
+            let item = item.trim();
+            if item.is_empty() || !item.bytes().all(|byte| byte.is_ascii_digit()) {
+                return Err(ConfigError::InvalidUserScopeUids);
+            }
+            item.parse::<u32>()
+                .map_err(|_| ConfigError::InvalidUserScopeUids)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    uids.sort_unstable();
+    uids.dedup();
+    Ok(uids)
+}
+
+/// Parses the optional comma-separated machine allowlist for machine scopes.
+///
+/// Each item is a machine name, optionally followed by `=<dbus address>` to
+/// bypass the machined lookup of the container bus. Absent or blank values
+/// allow no machines; invalid names, unparsable addresses, empty items, and
+/// duplicate names are rejected.
+fn parse_machine_scopes(raw: Option<&str>) -> Result<MachineBuses, ConfigError> {
+    let Some(raw) = raw.map(str::trim).filter(|value| !value.is_empty()) else {
+        return Ok(MachineBuses::new());
+    };
+    let mut machines = MachineBuses::new();
+    for item in raw.split(',') {
+        let (name, address) = match item.split_once('=') {
+            Some((name, address)) => (name, Some(address.trim())),
+            None => (item, None),
+        };
+        let name = MachineName::parse(name.trim()).ok_or(ConfigError::InvalidMachineScopes)?;
+        if address.is_some_and(|address| address.parse::<zbus::Address>().is_err()) {
+            return Err(ConfigError::InvalidMachineScopes);
+        }
+        if machines
+            .insert(name, address.map(ToString::to_string))
+            .is_some()
+        {
+            return Err(ConfigError::InvalidMachineScopes);
+        }
+    }
+    Ok(machines)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -182,6 +275,9 @@ mod tests {
             bind_port: bind_port.map(ToString::to_string),
             rate_limit_requests_per_second: rate_limit_requests_per_second.map(ToString::to_string),
             rate_limit_burst: rate_limit_burst.map(ToString::to_string),
+            unit_cache_enabled: None,
+            user_scope_uids: None,
+            machine_scopes: None,
         }
     }
 
@@ -197,6 +293,9 @@ mod tests {
             DEFAULT_REQUESTS_PER_SECOND
         );
         assert_eq!(config.rate_limit_burst, DEFAULT_BURST);
+        assert!(!config.unit_cache_enabled);
+        assert!(config.user_scope_uids.is_empty());
+        assert!(config.machine_buses.is_empty());
     }
 
     #[test]
@@ -262,4 +361,80 @@ mod tests {
             ));
         }
     }
+
+    /// Verifies the unit cache flag accepts booleans and rejects other values.
+    #[test]
+    fn unit_cache_flag_parses_booleans_only() {
+        for (value, expected) in [("true", true), ("1", true), ("FALSE", false), ("0", false)] {
+            let mut raw = raw_config(Some("abcdefghijklmnop"), None, None, None, None);
+            raw.unit_cache_enabled = Some(value.to_string());
+            let config = Config::parse(raw).expect("config should parse");
+            assert_eq!(config.unit_cache_enabled, expected);
+        }
+
+        let mut raw = raw_config(Some("abcdefghijklmnop"), None, None, None, None);
+        raw.unit_cache_enabled = Some("yes-please".to_string());
+        assert!(matches!(
+            Config::parse(raw),
+            Err(ConfigError::InvalidUnitCacheEnabled)
+        ));
+    }
+
+    /// Verifies the user-scope allowlist is sorted, deduplicated, and strict.
+    #[test]
+    fn user_scope_uids_parse_as_numeric_list() {
+        let mut raw = raw_config(Some("abcdefghijklmnop"), None, None, None, None);
+        raw.user_scope_uids = Some(" 1001, 1000 ,1001".to_string());
+        let config = Config::parse(raw).expect("config should parse");
+        assert_eq!(config.user_scope_uids, vec![1000, 1001]);
+
+        for invalid in ["1000,", "alice", "-1", "+1000", "4294967296", "1000;1001"] {
+            let mut raw = raw_config(Some("abcdefghijklmnop"), None, None, None, None);
+            raw.user_scope_uids = Some(invalid.to_string());
+            assert!(matches!(
+                Config::parse(raw),
+                Err(ConfigError::InvalidUserScopeUids)
+            ));
+        }
+    }
+
+    /// Verifies machine scopes keep optional bus addresses and reject bad items.
+    #[test]
+    fn machine_scopes_parse_names_and_addresses() {
+        let mut raw = raw_config(Some("abcdefghijklmnop"), None, None, None, None);
+        raw.machine_scopes = Some(
+            " web-1 , db=unix:path=/var/lib/machines/db/run/dbus/system_bus_socket".to_string(),
+        );
+        let config = Config::parse(raw).expect("config should parse");
+        let machines = config
+            .machine_buses
+            .iter()
+            .map(|(name, address)| (name.as_str(), address.as_deref()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            machines,
+            vec![
+                (
+                    "db",
+                    Some("unix:path=/var/lib/machines/db/run/dbus/system_bus_socket")
+                ),
+                ("web-1", None),
+            ]
+        );
+
+        for invalid in [
+            "web-1,",
+            "../web",
+            "web-1,web-1",
+            "db=not-an-address",
+            "-db",
+        ] {
+            let mut raw = raw_config(Some("abcdefghijklmnop"), None, None, None, None);
+            raw.machine_scopes = Some(invalid.to_string());
+            assert!(matches!(
+                Config::parse(raw),
+                Err(ConfigError::InvalidMachineScopes)
+            ));
+        }
+    }
 }
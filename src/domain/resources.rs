@@ -117,6 +117,7 @@ pub async fn handle_resources_read(
                 end_utc: Some(end_utc),
                 limit: DEFAULT_LOG_LIMIT,
                 cursor: None,
+                boot_id: None,
             };
 
             match state.unit_provider.list_journal_logs(&query).await {
@@ -9,6 +9,7 @@ use rust_mcp_sdk::schema::{
     TextResourceContents,
 };
 use serde_json::{Map, Value};
+use std::time::Duration;
 
 use crate::mcp::rpc::json_rpc_result;
 
@@ -27,6 +28,15 @@ pub fn generated_at_utc_string() -> String {
     Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)
 }
 
+/// Converts a provider cache age into the `cache_age_ms` response field.
+///
+/// Live (uncached) reads report `null`.
+pub fn cache_age_ms(age: Option<Duration>) -> Value {
+    age.map_or(Value::Null, |age| {
+        Value::from(u64::try_from(age.as_millis()).unwrap_or(u64::MAX))
+    })
+}
+
 /// Applies a result limit and returns consistent pagination metadata.
 ///
 /// `total` is the number of matching rows before limiting, `returned` is the
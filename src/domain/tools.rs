@@ -2,11 +2,28 @@
 //!
 //! Provides MCP tool catalog and dispatch for service, timer, and log monitoring.
 
+mod boot_timeline;
+mod boots;
+mod coredumps;
+mod dependencies;
+mod diagnose;
+mod explain_timer;
+mod host;
+mod jobs;
 mod logs;
+mod machines;
 mod podman;
+mod security;
 mod services;
+mod sessions;
+mod storage;
+mod timer_history;
 mod timers;
+mod top_units;
+mod unit_file;
+mod unit_resources;
 mod unit_status;
+mod units;
 
 use rust_mcp_sdk::{
     macros,
@@ -28,6 +45,18 @@ pub struct ServicesQueryParams {
     pub name_contains: Option<String>,
     pub limit: Option<u32>,
     pub summary: Option<bool>,
+    pub include_resources: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UnitsQueryParams {
+    pub scope: Option<String>,
+    #[serde(rename = "type")]
+    pub unit_type: Option<String>,
+    pub state: Option<String>,
+    pub name_contains: Option<String>,
+    pub limit: Option<u32>,
+    pub summary: Option<bool>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -51,7 +80,7 @@ pub struct LogsQueryParams {
 
 #[macros::mcp_tool(
     name = "get_unit_status",
-    description = "Inspect one systemd service with direct dependency failures and recent transitions."
+    description = "Inspect one systemd unit of any type with direct dependency failures and recent transitions. Timers, sockets, mounts, and paths add type_properties; mount options are credential-redacted."
 )]
 #[derive(Debug, Deserialize, Serialize, macros::JsonSchema)]
 pub struct GetUnitStatusTool {
@@ -82,9 +111,14 @@ pub struct GetPodStatusTool {
     name = "list_services",
     description = "List systemd service units and current state. \
 Optional filters should be omitted when unset. scope accepts system, user, \
-or both and defaults to system. state accepts active, inactive, failed, \
-activating, deactivating, or reloading. limit accepts 1-1000 and defaults \
-to 200."
+both, user:<uid>, users:all, or machine:<name> (allowlisted UIDs and machines only) and defaults to system. \
+state accepts active, inactive, failed, activating, deactivating, reloading, or flapping (3+ starts or failures in the \
+last 10 minutes, or start limit hit). Rows carry restart_count, start_limit, \
+flapping, pending_job (job_id and job_type of a queued job, see list_jobs), exit_code \
+(exited, killed, or dumped) with exec_main_status as the exit status or signal number \
+and exit_signal as its name (for example SIGKILL), and oom_killed with oom_kill_source \
+(result, kernel, or systemd-oomd). limit accepts 1-1000 and defaults to 200. include_resources=true adds cgroup memory, CPU, tasks, IO, and IP \
+counters and limits to each returned row."
 )]
 #[derive(Debug, Deserialize, Serialize, macros::JsonSchema)]
 pub struct ListServicesTool {
@@ -93,6 +127,310 @@ pub struct ListServicesTool {
     pub name_contains: Option<String>,
     pub limit: Option<u32>,
     pub summary: Option<bool>,
+    pub include_resources: Option<bool>,
+}
+
+#[macros::mcp_tool(
+    name = "list_units",
+    description = "List systemd units of every type (service, socket, target, \
+device, mount, automount, swap, timer, path, slice, scope) and current state. \
+Optional filters should be omitted when unset. type restricts results to one \
+unit type. scope accepts system, user, both, user:<uid>, users:all, or machine:<name> and \
+defaults to system. state \
+accepts active, inactive, failed, activating, deactivating, or reloading. \
+limit accepts 1-1000 and defaults to 200. Mounts include what/where, sockets \
+include listen addresses, and paths include watched paths in details."
+)]
+#[derive(Debug, Deserialize, Serialize, macros::JsonSchema)]
+pub struct ListUnitsTool {
+    pub scope: Option<String>,
+    #[serde(rename = "type")]
+    pub unit_type: Option<String>,
+    pub state: Option<String>,
+    pub name_contains: Option<String>,
+    pub limit: Option<u32>,
+    pub summary: Option<bool>,
+}
+
+#[macros::mcp_tool(
+    name = "get_unit_dependencies",
+    description = "Walk the dependency graph of one systemd unit with depth and node bounds. \
+direction=forward (default) follows Requires, Wants, BindsTo, PartOf, and After; \
+direction=reverse follows RequiredBy and WantedBy. scope accepts system, user, user:<uid>, or machine:<name>. \
+max_depth accepts 1-10 (default 3) and max_nodes 1-500 (default 100). Returns \
+nodes with state, edges, ordering (After) cycles, and a trace from the root to its \
+deepest failed dependency."
+)]
+#[derive(Debug, Deserialize, Serialize, macros::JsonSchema)]
+pub struct GetUnitDependenciesTool {
+    pub unit: String,
+    pub scope: Option<String>,
+    pub direction: Option<String>,
+    pub max_depth: Option<u32>,
+    pub max_nodes: Option<u32>,
+}
+
+#[macros::mcp_tool(
+    name = "get_unit_file",
+    description = "Read the unit file and drop-ins of one loaded systemd unit. Returns parsed \
+sections with per-entry source file, line, and drop-in override provenance, plus \
+FragmentPath, DropInPaths, SourcePath, and NeedDaemonReload. Environment=, \
+LoadCredential=, and SetCredential= values and credential-like command arguments \
+are redacted. Files outside the standard unit directories are skipped. scope \
+accepts system, user, user:<uid>, or machine:<name>."
+)]
+#[derive(Debug, Deserialize, Serialize, macros::JsonSchema)]
+pub struct GetUnitFileTool {
+    pub unit: String,
+    pub scope: Option<String>,
+}
+
+#[macros::mcp_tool(
+    name = "get_unit_resources",
+    description = "Read cgroup resource usage and limits for one service, slice, scope, \
+socket, mount, or swap unit: memory current/peak/high/max, CPU usage and quota, \
+tasks current/max, IO read/write bytes, and IP ingress/egress bytes. Unlimited \
+limits and disabled accounting are null. scope accepts system, user, user:<uid>, or machine:<name>."
+)]
+#[derive(Debug, Deserialize, Serialize, macros::JsonSchema)]
+pub struct GetUnitResourcesTool {
+    pub unit: String,
+    pub scope: Option<String>,
+}
+
+#[macros::mcp_tool(
+    name = "top_units",
+    description = "Rank running services by current resource use. metric accepts memory \
+(default), cpu, or tasks. CPU percent (of one core) is computed from two counter \
+samples interval_ms apart (100-5000, default 1000). scope accepts system, user, \
+both, user:<uid>, users:all, or machine:<name> and defaults to system; each row carries its scope. limit accepts 1-100 \
+and defaults to 10."
+)]
+#[derive(Debug, Deserialize, Serialize, macros::JsonSchema)]
+pub struct TopUnitsTool {
+    pub scope: Option<String>,
+    pub metric: Option<String>,
+    pub limit: Option<u32>,
+    pub interval_ms: Option<u64>,
+}
+
+#[macros::mcp_tool(
+    name = "analyze_unit_security",
+    description = "Score service sandboxing exposure like systemd-analyze security. \
+With unit (a .service; scope system, user, user:<uid>, or machine:<name>), returns every weighted hardening \
+check (User/DynamicUser, NoNewPrivileges, CapabilityBoundingSet, SystemCallFilter, \
+ProtectSystem, PrivateTmp, RestrictAddressFamilies, and more) with pass, partial, \
+exposed, or unknown status, an exposure score from 0 (hardened) to 10 (exposed), \
+a rating, and the findings list. Without unit, ranks loaded services by exposure; \
+scope accepts system, user, both, user:<uid>, users:all, or machine:<name> and limit accepts \
+1-1000 (default 200)."
+)]
+#[derive(Debug, Deserialize, Serialize, macros::JsonSchema)]
+pub struct AnalyzeUnitSecurityTool {
+    pub unit: Option<String>,
+    pub scope: Option<String>,
+    pub limit: Option<u32>,
+}
+
+#[macros::mcp_tool(
+    name = "get_boot_timeline",
+    description = "Report boot performance like systemd-analyze. Returns firmware, \
+loader, kernel, initrd, and userspace phase durations in microseconds, a blame list \
+of the slowest-activating units (limit 1-100, default 20), and the critical chain \
+of target (default the manager's default target) computed from After= ordering. \
+scope accepts system, user, user:<uid>, or machine:<name>."
+)]
+#[derive(Debug, Deserialize, Serialize, macros::JsonSchema)]
+pub struct GetBootTimelineTool {
+    pub scope: Option<String>,
+    pub target: Option<String>,
+    pub limit: Option<u32>,
+}
+
+#[macros::mcp_tool(
+    name = "list_boots",
+    description = "List boots recorded in the journal, newest first, with boot_id, \
+offset (0 is the newest boot, -1 the one before), current flag, and first/last \
+entry timestamps. Use boot_id or offset as list_logs boot. scope accepts system, \
+user, or both and defaults to system. limit accepts 1-1000 and defaults to 20."
+)]
+#[derive(Debug, Deserialize, Serialize, macros::JsonSchema)]
+pub struct ListBootsTool {
+    pub scope: Option<String>,
+    pub limit: Option<u32>,
+}
+
+#[macros::mcp_tool(
+    name = "list_sessions",
+    description = "List logind login sessions with session_id, uid, user, seat, class, \
+type, state (online, active, or closing; closing sessions that persist usually \
+left processes behind), remote and remote_host, tty, idle_hint, idle_since_utc, \
+and since_utc. Also returns every logged-in or lingering user with linger, state, \
+runtime_path, and session_ids, and every seat with its session_ids. user filters \
+sessions and users by exact user name. limit accepts 1-1000 and defaults to 200."
+)]
+#[derive(Debug, Deserialize, Serialize, macros::JsonSchema)]
+pub struct ListSessionsTool {
+    pub user: Option<String>,
+    pub limit: Option<u32>,
+}
+
+#[macros::mcp_tool(
+    name = "list_machines",
+    description = "List virtual machines and containers registered with systemd-machined \
+(for example systemd-nspawn containers) with name, class (container or vm), service, \
+leader_pid, root_directory, network_interfaces (host interface indices), state, \
+since_utc, and unit, the host scope or service holding the machine; pass unit to \
+get_unit_status with scope system. name returns only that machine. limit accepts \
+1-1000 and defaults to 200."
+)]
+#[derive(Debug, Deserialize, Serialize, macros::JsonSchema)]
+pub struct ListMachinesTool {
+    pub name: Option<String>,
+    pub limit: Option<u32>,
+}
+
+#[macros::mcp_tool(
+    name = "list_jobs",
+    description = "List jobs queued in the systemd manager (start, stop, restart, and \
+similar) with job_id, unit, job_type, state (waiting or running), first_seen_utc and \
+observed_for_ms (time since this server first listed the job, not its queue time; \
+null on the first listing), and waiting_on, the queued jobs (job_id, unit, job_type) that must finish \
+first. An empty list means nothing is pending. scope accepts system, user, both, \
+user:<uid>, users:all, or machine:<name> and defaults to system. limit accepts \
+1-1000 and defaults to 200."
+)]
+#[derive(Debug, Deserialize, Serialize, macros::JsonSchema)]
+pub struct ListJobsTool {
+    pub scope: Option<String>,
+    pub limit: Option<u32>,
+}
+
+#[macros::mcp_tool(
+    name = "explain_timer",
+    description = "Explain the schedule of one .timer unit: its OnCalendar expressions \
+(calendar, each with parsed and parse_error), monotonic triggers (monotonic, base and \
+offset_usec), accuracy_usec, randomized_delay_usec, and upcoming, the next count \
+predicted elapses from the calendar with utc, local (in time_zone), and the \
+expression producing it. missed_runs counts calendar elapses between last_run_utc \
+and now that passed their grace without a trigger (count, capped at 1000, \
+first_missed_utc, last_missed_utc). overdue and overdue_reason follow list_timers. \
+Predictions ignore randomized delay. scope accepts system, user, user:<uid>, or \
+machine:<name> and defaults to system. count accepts 1-50 and defaults to 5. \
+time_zone accepts UTC or an IANA name such as Europe/Berlin and defaults to UTC."
+)]
+#[derive(Debug, Deserialize, Serialize, macros::JsonSchema)]
+pub struct ExplainTimerTool {
+    pub unit: String,
+    pub scope: Option<String>,
+    pub count: Option<u32>,
+    pub time_zone: Option<String>,
+}
+
+#[macros::mcp_tool(
+    name = "get_timer_history",
+    description = "Report the runs of a .timer unit's trigger_unit between start_utc and \
+end_utc, reconstructed from systemd's journal messages about the unit: runs (newest \
+first) with started_utc, finished_utc, duration_ms, outcome (success, failed, or \
+running), result (such as exit-code or timeout), exit_code, and exit_status, and a \
+summary over all runs in the window with total, succeeded, failed, running, \
+success_ratio, avg_duration_ms, p95_duration_ms, and last_failure. Manual starts of \
+the trigger unit are included. history_truncated is true when the window held more \
+journal messages than are read; the newest are kept and the summary covers only them. scope accepts system, user, user:<uid>, or \
+machine:<name> and defaults to system. end_utc defaults to now and start_utc to 7 \
+days before it; the window may not exceed 31 days. limit accepts 1-200 and \
+defaults to 50."
+)]
+#[derive(Debug, Deserialize, Serialize, macros::JsonSchema)]
+pub struct GetTimerHistoryTool {
+    pub unit: String,
+    pub scope: Option<String>,
+    pub start_utc: Option<String>,
+    pub end_utc: Option<String>,
+    pub limit: Option<u32>,
+}
+
+#[macros::mcp_tool(
+    name = "list_coredumps",
+    description = "List process crashes recorded by systemd-coredump in the journal, \
+newest first by default: timestamp_utc, executable, command, pid, uid, signal (such \
+as SIGSEGV), unit, boot_id, core_stored (whether the core file is still on disk), \
+and stack_trace, at most 16 frames of the crashing thread with stack_trace_truncated. \
+Core contents, command lines, and environments are never returned. start_utc and \
+end_utc are required and follow list_logs (at most 7 days unless \
+allow_large_window=true). unit filters by the crashed unit and executable by exact \
+absolute path. scope accepts system, user:<uid>, or machine:<name> and defaults to \
+system. order accepts asc or desc and defaults to desc. limit accepts 1-1000 and \
+defaults to 200."
+)]
+#[derive(Debug, Deserialize, Serialize, macros::JsonSchema)]
+pub struct ListCoredumpsTool {
+    pub scope: Option<String>,
+    pub unit: Option<String>,
+    pub executable: Option<String>,
+    pub start_utc: Option<String>,
+    pub end_utc: Option<String>,
+    pub allow_large_window: Option<bool>,
+    pub order: Option<String>,
+    pub limit: Option<u32>,
+}
+
+#[macros::mcp_tool(
+    name = "diagnose_unit",
+    description = "Triage one systemd unit in a single call. Returns status (as \
+get_unit_status), exit (result, exit_code, exec_main_status, exit_signal, main process \
+start and exit times, oom_killed, oom_kill_source), restarts (restart_count, \
+start_limit, flapping), failed_dependencies, recent_transitions, error_logs (the newest \
+log_limit entries of priority err or worse, with since last_start, last_7d when that \
+start is older than 7 days, or last_24h when no start is recorded), triggered_by (the timer whose \
+trigger unit this is, or null), and likely_causes, rule-based findings with cause \
+(missing_dependency, failed_dependency, oom_kill, start_limit_hit, or exec_not_found) \
+and evidence. Sections that could not be read are named in unavailable. scope accepts \
+system, user, user:<uid>, or machine:<name> and defaults to system. log_limit accepts \
+1-100 and defaults to 20. transition_limit accepts 1-100 and defaults to 10."
+)]
+#[derive(Debug, Deserialize, Serialize, macros::JsonSchema)]
+pub struct DiagnoseUnitTool {
+    pub unit: String,
+    pub scope: Option<String>,
+    pub log_limit: Option<u32>,
+    pub transition_limit: Option<u32>,
+}
+
+#[macros::mcp_tool(
+    name = "get_host_overview",
+    description = "Report basic facts about the monitored host: manager (system manager \
+version, system_state, virtualization, architecture, failed_units, and jobs), host \
+(hostname, static_hostname, pretty_hostname, chassis, operating_system, kernel_name, \
+and kernel_release from systemd-hostnamed), time (timezone, local_rtc, ntp_enabled, \
+and ntp_synchronized from systemd-timedated), uptime_seconds, booted_at_utc, \
+load_average (one_minute, five_minutes, fifteen_minutes), and memory \
+(memory_total_bytes, memory_available_bytes, swap_total_bytes, swap_free_bytes). \
+host and time are null when those services cannot be reached. Takes no arguments."
+)]
+#[derive(Debug, Deserialize, Serialize, macros::JsonSchema)]
+pub struct GetHostOverviewTool {}
+
+#[macros::mcp_tool(
+    name = "get_storage_status",
+    description = "Report filesystem capacity and journal disk usage. mounts lists real \
+filesystems from /proc/self/mountinfo (pseudo filesystems such as proc, sysfs, cgroup, \
+and devtmpfs and read-only images such as squashfs are skipped), sorted by mount_point, \
+with mount_unit (the system .mount unit when one exists), source, fs_type, read_only, \
+size_bytes, used_bytes, available_bytes, bytes_used_percent (used over used plus \
+available, as df), inodes_total, inodes_used, inodes_free, inodes_used_percent, and \
+over_bytes_threshold and over_inodes_threshold. Network and FUSE mounts that do not \
+answer within 2 seconds have stat_timed_out=true, zero counts, and null percentages. \
+over_threshold lists every flagged mount_point. journal reports total_bytes and directories under /var/log/journal \
+(persistent) and /run/log/journal with files and bytes. bytes_threshold_percent and \
+inodes_threshold_percent accept 1-100 and default to 90. limit accepts 1-1000 and \
+defaults to 200."
+)]
+#[derive(Debug, Deserialize, Serialize, macros::JsonSchema)]
+pub struct GetStorageStatusTool {
+    pub bytes_threshold_percent: Option<u32>,
+    pub inodes_threshold_percent: Option<u32>,
+    pub limit: Option<u32>,
 }
 
 #[macros::mcp_tool(
@@ -102,8 +440,12 @@ Optional filters must be omitted when unset: do not send priority=\".*\" \
 for all priorities, and do not send unit=\"\" for all units. \
 priority accepts journald severity thresholds 0-7 and aliases: emerg, alert, \
 crit, err, warning, notice, info, debug. Use grep for message substring or \
-regex-lite matching. start_utc is required unless since_last_start=true; \
-when since_last_start=true, omit start_utc and provide exactly one unit."
+regex-lite matching. start_utc is required unless since_last_start=true or boot \
+is set; end_utc is required unless boot is set. When since_last_start=true, omit \
+start_utc and provide exactly one unit. boot accepts current, a non-positive offset \
+such as -1 (previous boot), or a boot_id from list_boots, and replaces \
+start_utc/end_utc. scope accepts system, user, both, user:<uid>, users:all, or machine:<name>; \
+per-user entries carry owner_uid and machine entries carry machine."
 )]
 #[derive(Debug, Deserialize, Serialize, macros::JsonSchema)]
 pub struct ListLogsTool {
@@ -111,7 +453,7 @@ pub struct ListLogsTool {
     pub priority: Option<String>,
     pub unit: Option<String>,
     pub start_utc: Option<String>,
-    pub end_utc: String,
+    pub end_utc: Option<String>,
     pub grep: Option<String>,
     pub exclude_units: Option<Vec<String>>,
     pub order: Option<String>,
@@ -122,15 +464,20 @@ pub struct ListLogsTool {
     pub fields: Option<Vec<String>>,
     pub group_by: Option<String>,
     pub since_last_start: Option<bool>,
+    pub boot: Option<String>,
 }
 
 #[macros::mcp_tool(
     name = "list_timers",
     description = "List systemd timer units and scheduling/trigger state. \
 Optional filters should be omitted when unset. scope accepts system, user, \
-or both and defaults to system. state is a non-empty active-state filter. \
+both, user:<uid>, users:all, or machine:<name> and defaults to system. state is a non-empty active-state filter. \
 sort accepts next, last, name, or state and defaults to name; order accepts \
-asc or desc and defaults to asc. limit accepts 1-1000 and defaults to 200."
+asc or desc and defaults to asc. limit accepts 1-1000 and defaults to 200. Rows \
+include calendar, monotonic, accuracy_usec, and randomized_delay_usec; overdue \
+allows 5 minutes plus accuracy and randomized delay past a due time and \
+overdue_reason missed_scheduled_runs flags calendar runs missed since the last \
+trigger (see explain_timer)."
 )]
 #[derive(Debug, Deserialize, Serialize, macros::JsonSchema)]
 pub struct ListTimersTool {
@@ -157,6 +504,23 @@ pub fn build_tools_list() -> Vec<Tool> {
         GetUnitStatusTool::tool(),
         GetContainerStatusTool::tool(),
         GetPodStatusTool::tool(),
+        ListUnitsTool::tool(),
+        GetUnitDependenciesTool::tool(),
+        GetUnitFileTool::tool(),
+        GetUnitResourcesTool::tool(),
+        TopUnitsTool::tool(),
+        AnalyzeUnitSecurityTool::tool(),
+        GetBootTimelineTool::tool(),
+        ListBootsTool::tool(),
+        ListSessionsTool::tool(),
+        ListMachinesTool::tool(),
+        ListJobsTool::tool(),
+        ExplainTimerTool::tool(),
+        GetTimerHistoryTool::tool(),
+        ListCoredumpsTool::tool(),
+        DiagnoseUnitTool::tool(),
+        GetHostOverviewTool::tool(),
+        GetStorageStatusTool::tool(),
     ]
 }
 
@@ -185,6 +549,23 @@ pub async fn handle_tools_call(
         "get_pod_status" => podman::handle_pod(state, id, tool_call.arguments).await,
         "list_timers" => timers::handle_list_timers(state, id, tool_call.arguments).await,
         "list_logs" => logs::handle_list_logs(state, id, tool_call.arguments).await,
+        "list_units" => units::handle_list_units(state, id, tool_call.arguments).await,
+        "get_unit_dependencies" => dependencies::handle(state, id, tool_call.arguments).await,
+        "get_unit_file" => unit_file::handle(state, id, tool_call.arguments).await,
+        "get_unit_resources" => unit_resources::handle(state, id, tool_call.arguments).await,
+        "top_units" => top_units::handle(state, id, tool_call.arguments).await,
+        "analyze_unit_security" => security::handle(state, id, tool_call.arguments).await,
+        "get_boot_timeline" => boot_timeline::handle(state, id, tool_call.arguments).await,
+        "list_boots" => boots::handle(state, id, tool_call.arguments).await,
+        "list_sessions" => sessions::handle(state, id, tool_call.arguments).await,
+        "list_machines" => machines::handle(state, id, tool_call.arguments).await,
+        "list_jobs" => jobs::handle(state, id, tool_call.arguments).await,
+        "explain_timer" => explain_timer::handle(state, id, tool_call.arguments).await,
+        "get_timer_history" => timer_history::handle(state, id, tool_call.arguments).await,
+        "list_coredumps" => coredumps::handle(state, id, tool_call.arguments).await,
+        "diagnose_unit" => diagnose::handle(state, id, tool_call.arguments).await,
+        "get_host_overview" => host::handle(state, id, tool_call.arguments).await,
+        "get_storage_status" => storage::handle(state, id, tool_call.arguments).await,
         _ => json_rpc_method_not_found_with_data(
             id,
             json!({
@@ -382,6 +763,10 @@ mod tests {
                 result: None,
                 load_state: Some("loaded".to_string()),
                 unit_file_state: Some("enabled".to_string()),
+                calendar: Vec::new(),
+                monotonic: Vec::new(),
+                accuracy_usec: None,
+                randomized_delay_usec: None,
                 overdue: false,
                 overdue_reason: Some("no_next_run_known".to_string()),
             },
@@ -399,6 +784,10 @@ mod tests {
                 result: None,
                 load_state: Some("loaded".to_string()),
                 unit_file_state: Some("enabled".to_string()),
+                calendar: Vec::new(),
+                monotonic: Vec::new(),
+                accuracy_usec: None,
+                randomized_delay_usec: None,
                 overdue: false,
                 overdue_reason: None,
             },
@@ -427,6 +816,10 @@ mod tests {
                 result: None,
                 load_state: Some("loaded".to_string()),
                 unit_file_state: Some("enabled".to_string()),
+                calendar: Vec::new(),
+                monotonic: Vec::new(),
+                accuracy_usec: None,
+                randomized_delay_usec: None,
                 overdue: false,
                 overdue_reason: None,
             },
@@ -444,6 +837,10 @@ mod tests {
                 result: None,
                 load_state: Some("loaded".to_string()),
                 unit_file_state: Some("enabled".to_string()),
+                calendar: Vec::new(),
+                monotonic: Vec::new(),
+                accuracy_usec: None,
+                randomized_delay_usec: None,
                 overdue: false,
                 overdue_reason: None,
             },
@@ -461,6 +858,10 @@ mod tests {
                 result: None,
                 load_state: Some("loaded".to_string()),
                 unit_file_state: Some("enabled".to_string()),
+                calendar: Vec::new(),
+                monotonic: Vec::new(),
+                accuracy_usec: None,
+                randomized_delay_usec: None,
                 overdue: false,
                 overdue_reason: None,
             },
@@ -0,0 +1,85 @@
+//! Boot performance timeline MCP handler.
+
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::{
+    AppState,
+    domain::{
+        responses::{generated_at_utc_string, tool_success_response},
+        utils::{SINGLE_SCOPE_MESSAGE, normalize_scope, normalize_typed_unit},
+    },
+    errors::AppError,
+    mcp::rpc::{app_error_to_json_rpc, json_rpc_invalid_params},
+};
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    scope: Option<String>,
+    target: Option<String>,
+    limit: Option<u32>,
+}
+
+/// Validates and handles `get_boot_timeline` for a concrete service manager scope.
+pub async fn handle(
+    state: &AppState,
+    id: Option<Value>,
+    arguments: Option<serde_json::Map<String, Value>>,
+) -> Value {
+    let params: Params = match serde_json::from_value(json!(arguments.unwrap_or_default())) {
+        Ok(value) => value,
+        Err(_) => return json_rpc_invalid_params(id),
+    };
+    let scope = match normalize_scope(params.scope) {
+        Ok(scope) if !scope.is_aggregate() => scope,
+        _ => {
+            return app_error_to_json_rpc(
+                id,
+                AppError::bad_request("invalid_scope", SINGLE_SCOPE_MESSAGE),
+            );
+        }
+    };
+    let target = match params.target.map(normalize_typed_unit).transpose() {
+        Ok(value) => value,
+        Err(err) => return app_error_to_json_rpc(id, err),
+    };
+    let limit = params.limit.unwrap_or(20);
+    if !(1..=100).contains(&limit) {
+        return app_error_to_json_rpc(
+            id,
+            AppError::bad_request("invalid_limit", "limit must be between 1 and 100"),
+        );
+    }
+
+    match state
+        .unit_provider
+        .get_boot_timeline(scope, target.as_deref(), limit as usize)
+        .await
+    {
+        Ok(timeline) => {
+            let message = match (timeline.finished, timeline.total_usec) {
+                (true, Some(total)) => format!(
+                    "Boot finished in {:.3}s; critical chain has {} units",
+                    total as f64 / 1_000_000.0,
+                    timeline.critical_chain.len()
+                ),
+                _ => "Boot has not finished yet".to_string(),
+            };
+            let mut payload = match serde_json::to_value(timeline) {
+                Ok(Value::Object(object)) => object,
+                _ => {
+                    return app_error_to_json_rpc(
+                        id,
+                        AppError::internal("failed to serialize boot timeline"),
+                    );
+                }
+            };
+            payload.insert(
+                "generated_at_utc".to_string(),
+                json!(generated_at_utc_string()),
+            );
+            tool_success_response(id, message, payload)
+        }
+        Err(err) => app_error_to_json_rpc(id, err),
+    }
+}
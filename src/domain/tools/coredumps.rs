@@ -0,0 +1,125 @@
+//! systemd-coredump crash listing MCP handler.
+
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::{
+    AppState,
+    domain::{
+        responses::{generated_at_utc_string, tool_success_response},
+        tools::{LogsQueryParams, build_log_query},
+    },
+    errors::AppError,
+    mcp::rpc::{app_error_to_json_rpc, json_rpc_invalid_params},
+    systemd_client::UnitScope,
+};
+
+/// Longest accepted executable path, matching `PATH_MAX`.
+const MAX_EXECUTABLE_LENGTH: usize = 4096;
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    scope: Option<String>,
+    unit: Option<String>,
+    executable: Option<String>,
+    start_utc: Option<String>,
+    end_utc: Option<String>,
+    allow_large_window: Option<bool>,
+    order: Option<String>,
+    limit: Option<u32>,
+}
+
+/// Validates and handles `list_coredumps` with the `list_logs` window, unit,
+/// order, and limit rules.
+pub async fn handle(
+    state: &AppState,
+    id: Option<Value>,
+    arguments: Option<serde_json::Map<String, Value>>,
+) -> Value {
+    let params: Params = match serde_json::from_value(json!(arguments.unwrap_or_default())) {
+        Ok(value) => value,
+        Err(_) => return json_rpc_invalid_params(id),
+    };
+    let executable = match normalize_executable(params.executable) {
+        Ok(value) => value,
+        Err(err) => return app_error_to_json_rpc(id, err),
+    };
+    let query = match build_log_query(LogsQueryParams {
+        scope: params.scope,
+        priority: None,
+        unit: params.unit,
+        start_utc: params.start_utc,
+        end_utc: params.end_utc,
+        grep: None,
+        exclude_units: None,
+        order: params.order,
+        allow_large_window: params.allow_large_window,
+        limit: params.limit,
+        summary: None,
+        cursor: None,
+        fields: None,
+        group_by: None,
+        since_last_start: None,
+    }) {
+        Ok(query) => query,
+        Err(err) => return app_error_to_json_rpc(id, err),
+    };
+    if !matches!(
+        query.scope,
+        UnitScope::System | UnitScope::UserId(_) | UnitScope::Machine(_)
+    ) {
+        return app_error_to_json_rpc(
+            id,
+            AppError::bad_request(
+                "invalid_scope",
+                "scope must be system, user:<uid>, or machine:<name>",
+            ),
+        );
+    }
+
+    match state
+        .unit_provider
+        .list_coredumps(&query, executable.as_deref())
+        .await
+    {
+        Ok(mut coredumps) => {
+            let truncated = coredumps.len() > query.limit;
+            coredumps.truncate(query.limit);
+            tool_success_response(
+                id,
+                format!("Returned {} coredumps", coredumps.len()),
+                serde_json::Map::from_iter([
+                    ("returned".to_string(), json!(coredumps.len())),
+                    ("coredumps".to_string(), json!(coredumps)),
+                    ("scope".to_string(), json!(query.scope.as_str())),
+                    ("truncated".to_string(), json!(truncated)),
+                    (
+                        "generated_at_utc".to_string(),
+                        json!(generated_at_utc_string()),
+                    ),
+                ]),
+            )
+        }
+        Err(err) => app_error_to_json_rpc(id, err),
+    }
+}
+
+/// Validates an optional absolute executable path filter.
+fn normalize_executable(executable: Option<String>) -> Result<Option<String>, AppError> {
+    let Some(executable) = executable
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+    else {
+        return Ok(None);
+    };
+    if !executable.starts_with('/')
+        || executable.len() > MAX_EXECUTABLE_LENGTH
+        || executable.chars().any(char::is_control)
+    {
+        return Err(AppError::bad_request(
+            "invalid_executable",
+            "executable must be an absolute path",
+        ));
+    }
+    Ok(Some(executable))
+}
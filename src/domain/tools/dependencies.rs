@@ -0,0 +1,103 @@
+//! Recursive systemd unit dependency graph MCP handler.
+
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::{
+    AppState,
+    domain::{
+        responses::{generated_at_utc_string, tool_success_response},
+        utils::{
+            SINGLE_SCOPE_MESSAGE, normalize_dependency_direction, normalize_scope,
+            normalize_typed_unit,
+        },
+    },
+    errors::AppError,
+    mcp::rpc::{app_error_to_json_rpc, json_rpc_invalid_params},
+};
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    unit: String,
+    scope: Option<String>,
+    direction: Option<String>,
+    max_depth: Option<u32>,
+    max_nodes: Option<u32>,
+}
+
+/// Validates and handles `get_unit_dependencies` for a concrete service manager scope.
+pub async fn handle(
+    state: &AppState,
+    id: Option<Value>,
+    arguments: Option<serde_json::Map<String, Value>>,
+) -> Value {
+    let params: Params = match serde_json::from_value(json!(arguments.unwrap_or_default())) {
+        Ok(value) => value,
+        Err(_) => return json_rpc_invalid_params(id),
+    };
+    let unit = match normalize_typed_unit(params.unit) {
+        Ok(value) => value,
+        Err(err) => return app_error_to_json_rpc(id, err),
+    };
+    let scope = match normalize_scope(params.scope) {
+        Ok(scope) if !scope.is_aggregate() => scope,
+        _ => {
+            return app_error_to_json_rpc(
+                id,
+                AppError::bad_request("invalid_scope", SINGLE_SCOPE_MESSAGE),
+            );
+        }
+    };
+    let direction = match normalize_dependency_direction(params.direction) {
+        Ok(value) => value,
+        Err(err) => return app_error_to_json_rpc(id, err),
+    };
+    let max_depth = params.max_depth.unwrap_or(3);
+    if !(1..=10).contains(&max_depth) {
+        return app_error_to_json_rpc(
+            id,
+            AppError::bad_request("invalid_max_depth", "max_depth must be between 1 and 10"),
+        );
+    }
+    let max_nodes = params.max_nodes.unwrap_or(100);
+    if !(1..=500).contains(&max_nodes) {
+        return app_error_to_json_rpc(
+            id,
+            AppError::bad_request("invalid_max_nodes", "max_nodes must be between 1 and 500"),
+        );
+    }
+    match state
+        .unit_provider
+        .get_unit_dependencies(
+            &unit,
+            scope,
+            direction,
+            max_depth as usize,
+            max_nodes as usize,
+        )
+        .await
+    {
+        Ok(graph) => {
+            let message = format!(
+                "Returned {} dependency nodes and {} edges",
+                graph.nodes.len(),
+                graph.edges.len()
+            );
+            let mut payload = match serde_json::to_value(graph) {
+                Ok(Value::Object(object)) => object,
+                _ => {
+                    return app_error_to_json_rpc(
+                        id,
+                        AppError::internal("failed to serialize dependency graph".to_string()),
+                    );
+                }
+            };
+            payload.insert(
+                "generated_at_utc".to_string(),
+                json!(generated_at_utc_string()),
+            );
+            tool_success_response(id, message, payload)
+        }
+        Err(err) => app_error_to_json_rpc(id, err),
+    }
+}
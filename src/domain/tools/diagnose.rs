@@ -0,0 +1,426 @@
+//! One-call unit triage bundle MCP handler.
+
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::{
+    AppState,
+    domain::{
+        responses::{generated_at_utc_string, tool_success_response},
+        tools::{LogsQueryParams, build_log_query},
+        utils::{SINGLE_SCOPE_MESSAGE, normalize_scope, normalize_typed_unit},
+    },
+    errors::AppError,
+    mcp::rpc::{app_error_to_json_rpc, json_rpc_invalid_params},
+    systemd_client::JournalLogEntry,
+};
+
+/// Log look-back when the unit has no recorded main-process start.
+const FALLBACK_LOG_WINDOW: Duration = Duration::hours(24);
+/// Largest log look-back, matching the `list_logs` window bound.
+const MAX_LOG_WINDOW: Duration = Duration::days(7);
+/// `EXIT_EXEC`, the status systemd reports when a unit's command could not be executed.
+const EXIT_EXEC_STATUS: i64 = 203;
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    unit: String,
+    scope: Option<String>,
+    log_limit: Option<u32>,
+    transition_limit: Option<u32>,
+}
+
+/// Validates and handles `diagnose_unit`, bundling status, exit details,
+/// dependency failures, transitions, error logs, and the triggering timer.
+///
+/// Only the unit status is required; log and timer lookups degrade to empty
+/// sections listed in `unavailable`.
+pub async fn handle(
+    state: &AppState,
+    id: Option<Value>,
+    arguments: Option<serde_json::Map<String, Value>>,
+) -> Value {
+    let params: Params = match serde_json::from_value(json!(arguments.unwrap_or_default())) {
+        Ok(value) => value,
+        Err(_) => return json_rpc_invalid_params(id),
+    };
+    let unit = match normalize_typed_unit(params.unit) {
+        Ok(value) => value,
+        Err(err) => return app_error_to_json_rpc(id, err),
+    };
+    let scope = match normalize_scope(params.scope) {
+        Ok(scope) if !scope.is_aggregate() => scope,
+        _ => {
+            return app_error_to_json_rpc(
+                id,
+                AppError::bad_request("invalid_scope", SINGLE_SCOPE_MESSAGE),
+            );
+        }
+    };
+    let log_limit = params.log_limit.unwrap_or(20);
+    if !(1..=100).contains(&log_limit) {
+        return app_error_to_json_rpc(
+            id,
+            AppError::bad_request("invalid_log_limit", "log_limit must be between 1 and 100"),
+        );
+    }
+    let transition_limit = params.transition_limit.unwrap_or(10);
+    if !(1..=100).contains(&transition_limit) {
+        return app_error_to_json_rpc(
+            id,
+            AppError::bad_request(
+                "invalid_transition_limit",
+                "transition_limit must be between 1 and 100",
+            ),
+        );
+    }
+
+    let mut status = match state
+        .unit_provider
+        .get_unit_status(&unit, scope, transition_limit as usize)
+        .await
+    {
+        Ok(status) => status,
+        Err(err) => return app_error_to_json_rpc(id, err),
+    };
+    let Some(object) = status.as_object_mut() else {
+        return app_error_to_json_rpc(
+            id,
+            AppError::internal("unit status did not serialize as an object"),
+        );
+    };
+    let failed_dependencies = object
+        .remove("failed_dependencies")
+        .unwrap_or_else(|| json!([]));
+    let recent_transitions = object
+        .remove("recent_transitions")
+        .unwrap_or_else(|| json!([]));
+    let mut unavailable = Vec::new();
+
+    let now = Utc::now();
+    let (log_start, logs_since) = error_log_window(
+        status
+            .pointer("/timestamps/main_process_start")
+            .and_then(Value::as_str)
+            .and_then(|value| DateTime::parse_from_rfc3339(value).ok())
+            .map(|start| start.with_timezone(&Utc)),
+        now,
+    );
+    let format = |at: DateTime<Utc>| at.to_rfc3339_opts(SecondsFormat::Millis, true);
+    let logs = match read_error_logs(
+        state,
+        &unit,
+        scope.as_str().into_owned(),
+        format(log_start),
+        format(now),
+        log_limit,
+    )
+    .await
+    {
+        Ok(logs) => logs,
+        Err(err) => {
+            tracing::warn!(unit = %unit, scope = %scope.as_str(), error = %err, "failed to read unit error logs");
+            unavailable.push("logs");
+            Vec::new()
+        }
+    };
+
+    let timer = match state.unit_provider.list_timer_units(scope).await {
+        Ok(timers) => timers
+            .into_iter()
+            .find(|timer| timer.trigger_unit.as_deref() == Some(unit.as_str()))
+            .map(|timer| {
+                json!({
+                    "unit": timer.unit,
+                    "active_state": timer.active_state,
+                    "last_run_utc": timer.last_run_utc,
+                    "next_run_utc": timer.next_run_utc,
+                    "result": timer.result,
+                })
+            }),
+        Err(err) => {
+            tracing::warn!(unit = %unit, scope = %scope.as_str(), error = %err, "failed to look up triggering timer");
+            unavailable.push("timer");
+            None
+        }
+    };
+
+    let causes = likely_causes(&status, &failed_dependencies, &logs);
+    let field = |name: &str| status.get(name).cloned().unwrap_or(Value::Null);
+    let timestamp = |name: &str| {
+        status
+            .get("timestamps")
+            .and_then(|timestamps| timestamps.get(name))
+            .cloned()
+            .unwrap_or(Value::Null)
+    };
+    let exit = json!({
+        "result": field("result"),
+        "exit_code": field("exit_code"),
+        "exec_main_status": field("exec_main_status"),
+        "exit_signal": field("exit_signal"),
+        "main_process_start_utc": timestamp("main_process_start"),
+        "main_process_exit_utc": timestamp("main_process_exit"),
+        "oom_killed": status.get("oom_killed").cloned().unwrap_or(json!(false)),
+        "oom_kill_source": field("oom_kill_source"),
+    });
+    let restarts = json!({
+        "restart_count": field("restart_count"),
+        "start_limit": field("start_limit"),
+        "flapping": status.get("flapping").cloned().unwrap_or(json!(false)),
+    });
+
+    tool_success_response(
+        id,
+        format!("Diagnosed {unit} ({} likely causes)", causes.len()),
+        serde_json::Map::from_iter([
+            ("unit".to_string(), json!(unit)),
+            ("scope".to_string(), json!(scope.as_str())),
+            ("status".to_string(), status),
+            ("exit".to_string(), exit),
+            ("restarts".to_string(), restarts),
+            ("failed_dependencies".to_string(), failed_dependencies),
+            ("recent_transitions".to_string(), recent_transitions),
+            (
+                "error_logs".to_string(),
+                json!({
+                    "since": logs_since,
+                    "start_utc": format(log_start),
+                    "end_utc": format(now),
+                    "entries": logs,
+                }),
+            ),
+            ("triggered_by".to_string(), json!(timer)),
+            ("likely_causes".to_string(), json!(causes)),
+            ("unavailable".to_string(), json!(unavailable)),
+            (
+                "generated_at_utc".to_string(),
+                json!(generated_at_utc_string()),
+            ),
+        ]),
+    )
+}
+
+/// Returns the error log window start and its `since` label.
+///
+/// The window opens at the last main-process start (`last_start`), clamped to
+/// `MAX_LOG_WINDOW` (`last_7d`), or covers `FALLBACK_LOG_WINDOW` without a
+/// recorded start (`last_24h`).
+fn error_log_window(
+    main_process_start: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> (DateTime<Utc>, &'static str) {
+    let (start, since) = match main_process_start {
+        Some(start) if start < now - MAX_LOG_WINDOW => (now - MAX_LOG_WINDOW, "last_7d"),
+        Some(start) => (start, "last_start"),
+        None => (now - FALLBACK_LOG_WINDOW, "last_24h"),
+    };
+    // A start stamped after `now` by clock skew would otherwise be an empty window.
+    (start.min(now - Duration::seconds(1)), since)
+}
+
+/// Reads the newest error-priority entries of `unit` through `list_logs` validation.
+async fn read_error_logs(
+    state: &AppState,
+    unit: &str,
+    scope: String,
+    start_utc: String,
+    end_utc: String,
+    limit: u32,
+) -> Result<Vec<JournalLogEntry>, AppError> {
+    let query = build_log_query(LogsQueryParams {
+        scope: Some(scope),
+        priority: Some("err".to_string()),
+        unit: Some(unit.to_string()),
+        start_utc: Some(start_utc),
+        end_utc: Some(end_utc),
+        grep: None,
+        exclude_units: None,
+        order: Some("desc".to_string()),
+        allow_large_window: None,
+        limit: Some(limit),
+        summary: None,
+        cursor: None,
+        fields: None,
+        group_by: None,
+        since_last_start: None,
+    })?;
+    Ok(state.unit_provider.list_journal_logs(&query).await?.entries)
+}
+
+/// Applies the triage rules to a unit status, its failed dependencies, and error logs.
+///
+/// Each cause carries a fixed `cause` code and `evidence` built from status
+/// fields only, so log message text is never repeated.
+pub(crate) fn likely_causes(
+    status: &Value,
+    failed_dependencies: &Value,
+    logs: &[JournalLogEntry],
+) -> Vec<Value> {
+    let mut causes = Vec::new();
+    let dependencies = failed_dependencies
+        .as_array()
+        .map_or(&[][..], Vec::as_slice);
+    for dependency in dependencies {
+        let text = |name: &str| dependency.get(name).and_then(Value::as_str).unwrap_or("");
+        let (cause, state) = if text("load_state") != "loaded" {
+            ("missing_dependency", text("load_state"))
+        } else {
+            ("failed_dependency", text("active_state"))
+        };
+        causes.push(json!({
+            "cause": cause,
+            "evidence": format!("{} dependency {} is {state}", text("relationship"), text("unit")),
+        }));
+    }
+
+    let result = status.get("result").and_then(Value::as_str);
+    if status.get("oom_killed").and_then(Value::as_bool) == Some(true) || result == Some("oom-kill")
+    {
+        let source = status
+            .get("oom_kill_source")
+            .and_then(Value::as_str)
+            .unwrap_or("result");
+        causes.push(json!({
+            "cause": "oom_kill",
+            "evidence": format!("out-of-memory kill reported by {source}"),
+        }));
+    }
+
+    let start_limit_hit = status
+        .get("start_limit")
+        .and_then(|value| value.get("hit"))
+        .and_then(Value::as_bool)
+        == Some(true);
+    if start_limit_hit || result == Some("start-limit-hit") {
+        causes.push(json!({
+            "cause": "start_limit_hit",
+            "evidence": "start attempts exceeded StartLimitBurst= within StartLimitIntervalSec=",
+        }));
+    }
+
+    let exec_status = status.get("exit_code").and_then(Value::as_str) == Some("exited")
+        && status.get("exec_main_status").and_then(Value::as_i64) == Some(EXIT_EXEC_STATUS);
+    let exec_log = logs.iter().find(|entry| {
+        entry
+            .message
+            .as_deref()
+            .is_some_and(|message| message.contains("Failed at step EXEC"))
+    });
+    if exec_status {
+        causes.push(json!({
+            "cause": "exec_not_found",
+            "evidence": "main process exited with status 203/EXEC",
+        }));
+    } else if let Some(entry) = exec_log {
+        causes.push(json!({
+            "cause": "exec_not_found",
+            "evidence": format!("error log at {} reports a failed EXEC step", entry.timestamp_utc),
+        }));
+    }
+    causes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log(message: &str) -> JournalLogEntry {
+        JournalLogEntry {
+            timestamp_utc: "2026-03-01T00:00:00.000Z".to_string(),
+            unit: Some("app.service".to_string()),
+            priority: Some("3".to_string()),
+            hostname: None,
+            pid: Some(42),
+            message: Some(message.to_string()),
+            cursor: None,
+            owner_uid: None,
+            machine: None,
+        }
+    }
+
+    fn codes(causes: &[Value]) -> Vec<&str> {
+        causes
+            .iter()
+            .filter_map(|cause| cause["cause"].as_str())
+            .collect()
+    }
+
+    #[test]
+    fn labels_clamped_error_log_windows() {
+        let now = DateTime::parse_from_rfc3339("2026-03-10T00:00:00Z")
+            .expect("timestamp")
+            .with_timezone(&Utc);
+
+        assert_eq!(
+            error_log_window(Some(now - Duration::hours(2)), now),
+            (now - Duration::hours(2), "last_start")
+        );
+        assert_eq!(
+            error_log_window(Some(now - Duration::days(30)), now),
+            (now - MAX_LOG_WINDOW, "last_7d")
+        );
+        assert_eq!(
+            error_log_window(Some(now + Duration::minutes(5)), now),
+            (now - Duration::seconds(1), "last_start")
+        );
+        assert_eq!(
+            error_log_window(None, now),
+            (now - FALLBACK_LOG_WINDOW, "last_24h")
+        );
+    }
+
+    #[test]
+    fn classifies_missing_and_failed_dependencies() {
+        let dependencies = json!([
+            {"unit": "ghost.service", "relationship": "requires", "load_state": "not-found", "active_state": "inactive"},
+            {"unit": "data.mount", "relationship": "wants", "load_state": "loaded", "active_state": "failed"},
+        ]);
+
+        let causes = likely_causes(&json!({}), &dependencies, &[]);
+
+        assert_eq!(codes(&causes), ["missing_dependency", "failed_dependency"]);
+        assert_eq!(
+            causes[0]["evidence"],
+            "requires dependency ghost.service is not-found"
+        );
+    }
+
+    #[test]
+    fn reports_oom_start_limit_and_exec_failures() {
+        let status = json!({
+            "result": "start-limit-hit",
+            "oom_killed": true,
+            "oom_kill_source": "kernel",
+            "exit_code": "exited",
+            "exec_main_status": 203,
+            "start_limit": {"burst": 5, "interval_usec": 10_000_000, "hit": false},
+        });
+
+        let causes = likely_causes(&status, &json!([]), &[]);
+
+        assert_eq!(
+            codes(&causes),
+            ["oom_kill", "start_limit_hit", "exec_not_found"]
+        );
+        assert_eq!(
+            causes[0]["evidence"],
+            "out-of-memory kill reported by kernel"
+        );
+    }
+
+    #[test]
+    fn detects_exec_failures_from_logs_without_echoing_messages() {
+        let status = json!({"result": "exit-code", "exit_code": "exited", "exec_main_status": 1});
+        let logs = [log(
+            "app.service: Failed at step EXEC spawning /opt/app/bin/run --token=secret: No such file or directory",
+        )];
+
+        let causes = likely_causes(&status, &json!([]), &logs);
+
+        assert_eq!(codes(&causes), ["exec_not_found"]);
+        assert!(!causes[0]["evidence"].as_str().unwrap().contains("secret"));
+        assert!(likely_causes(&status, &json!([]), &[log("ready")]).is_empty());
+    }
+}
@@ -0,0 +1,157 @@
+//! Timer schedule explanation MCP handler.
+
+use chrono::{SecondsFormat, Utc};
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::{
+    AppState,
+    calendar::merged_elapses,
+    domain::{
+        responses::{generated_at_utc_string, tool_success_response},
+        tools::timers::{missed_runs, parse_calendar_expression, timer_overdue_status},
+        utils::{SINGLE_SCOPE_MESSAGE, normalize_scope, normalize_typed_unit},
+    },
+    errors::AppError,
+    mcp::rpc::{app_error_to_json_rpc, json_rpc_invalid_params},
+    time_zone::TimeZone,
+};
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    unit: String,
+    scope: Option<String>,
+    count: Option<u32>,
+    time_zone: Option<String>,
+}
+
+/// Validates and handles `explain_timer`, predicting upcoming calendar elapses.
+pub async fn handle(
+    state: &AppState,
+    id: Option<Value>,
+    arguments: Option<serde_json::Map<String, Value>>,
+) -> Value {
+    let params: Params = match serde_json::from_value(json!(arguments.unwrap_or_default())) {
+        Ok(value) => value,
+        Err(_) => return json_rpc_invalid_params(id),
+    };
+    let unit = match normalize_typed_unit(params.unit) {
+        Ok(value) if value.ends_with(".timer") => value,
+        Ok(_) => {
+            return app_error_to_json_rpc(
+                id,
+                AppError::bad_request("invalid_unit", "unit must be a .timer unit"),
+            );
+        }
+        Err(err) => return app_error_to_json_rpc(id, err),
+    };
+    let scope = match normalize_scope(params.scope) {
+        Ok(scope) if !scope.is_aggregate() => scope,
+        _ => {
+            return app_error_to_json_rpc(
+                id,
+                AppError::bad_request("invalid_scope", SINGLE_SCOPE_MESSAGE),
+            );
+        }
+    };
+    let count = params.count.unwrap_or(5);
+    if !(1..=50).contains(&count) {
+        return app_error_to_json_rpc(
+            id,
+            AppError::bad_request("invalid_count", "count must be between 1 and 50"),
+        );
+    }
+    let time_zone = match params
+        .time_zone
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        Some(name) => match TimeZone::load(name) {
+            Ok(zone) => zone,
+            Err(err) => return app_error_to_json_rpc(id, err),
+        },
+        None => TimeZone::utc(),
+    };
+
+    let timers = match state.unit_provider.list_timer_units(scope).await {
+        Ok(timers) => timers,
+        Err(err) => return app_error_to_json_rpc(id, err),
+    };
+    let Some(timer) = timers.into_iter().find(|timer| timer.unit == unit) else {
+        return app_error_to_json_rpc(
+            id,
+            AppError::bad_request("unit_not_found", "systemd unit was not found"),
+        );
+    };
+
+    let now = Utc::now();
+    let local_zone = tokio::task::spawn_blocking(TimeZone::host_local)
+        .await
+        .unwrap_or_else(|_| TimeZone::utc());
+    let format = |at: &chrono::DateTime<Utc>| at.to_rfc3339_opts(SecondsFormat::Millis, true);
+
+    let mut calendar = Vec::with_capacity(timer.calendar.len());
+    let mut parsed = Vec::new();
+    let mut parsed_expressions = Vec::new();
+    for spec in &timer.calendar {
+        let parse_error = match parse_calendar_expression(&spec.expression) {
+            Ok(value) => {
+                parsed.push(value);
+                parsed_expressions.push(spec.expression.clone());
+                None
+            }
+            Err(reason) => Some(reason),
+        };
+        calendar.push(json!({
+            "base": spec.base,
+            "expression": spec.expression,
+            "next_elapse_utc": spec.next_elapse_utc,
+            "parsed": parse_error.is_none(),
+            "parse_error": parse_error,
+        }));
+    }
+
+    let upcoming = merged_elapses(&parsed, now, None, count as usize, &local_zone)
+        .into_iter()
+        .map(|(at, index)| {
+            json!({
+                "utc": format(&at),
+                "local": time_zone
+                    .to_local(at)
+                    .to_rfc3339_opts(SecondsFormat::Secs, false),
+                "expression": parsed_expressions[index],
+            })
+        })
+        .collect::<Vec<_>>();
+    let missed = missed_runs(&timer, &parsed, now, &local_zone);
+    let (overdue, overdue_reason) = timer_overdue_status(now, &timer, &local_zone);
+
+    tool_success_response(
+        id,
+        format!("Predicted {} upcoming runs of {unit}", upcoming.len()),
+        serde_json::Map::from_iter([
+            ("unit".to_string(), json!(timer.unit)),
+            ("scope".to_string(), json!(timer.scope)),
+            ("active_state".to_string(), json!(timer.active_state)),
+            ("calendar".to_string(), json!(calendar)),
+            ("monotonic".to_string(), json!(timer.monotonic)),
+            ("accuracy_usec".to_string(), json!(timer.accuracy_usec)),
+            (
+                "randomized_delay_usec".to_string(),
+                json!(timer.randomized_delay_usec),
+            ),
+            ("time_zone".to_string(), json!(time_zone.name())),
+            ("upcoming".to_string(), json!(upcoming)),
+            ("last_run_utc".to_string(), json!(timer.last_run_utc)),
+            ("next_run_utc".to_string(), json!(timer.next_run_utc)),
+            ("missed_runs".to_string(), json!(missed)),
+            ("overdue".to_string(), json!(overdue)),
+            ("overdue_reason".to_string(), json!(overdue_reason)),
+            (
+                "generated_at_utc".to_string(),
+                json!(generated_at_utc_string()),
+            ),
+        ]),
+    )
+}
@@ -0,0 +1,45 @@
+//! Host overview MCP handler.
+
+use serde_json::{Value, json};
+
+use crate::{
+    AppState,
+    domain::responses::{generated_at_utc_string, tool_success_response},
+    mcp::rpc::app_error_to_json_rpc,
+};
+
+/// Handles `get_host_overview`, reporting system manager, host identity, time,
+/// uptime, load, and memory facts.
+pub async fn handle(
+    state: &AppState,
+    id: Option<Value>,
+    _arguments: Option<serde_json::Map<String, Value>>,
+) -> Value {
+    match state.unit_provider.host_overview().await {
+        Ok(overview) => tool_success_response(
+            id,
+            format!(
+                "Host manager is {}",
+                overview
+                    .manager
+                    .system_state
+                    .as_deref()
+                    .unwrap_or("unknown")
+            ),
+            serde_json::Map::from_iter([
+                ("manager".to_string(), json!(overview.manager)),
+                ("host".to_string(), json!(overview.host)),
+                ("time".to_string(), json!(overview.time)),
+                ("uptime_seconds".to_string(), json!(overview.uptime_seconds)),
+                ("booted_at_utc".to_string(), json!(overview.booted_at_utc)),
+                ("load_average".to_string(), json!(overview.load_average)),
+                ("memory".to_string(), json!(overview.memory)),
+                (
+                    "generated_at_utc".to_string(),
+                    json!(generated_at_utc_string()),
+                ),
+            ]),
+        ),
+        Err(err) => app_error_to_json_rpc(id, err),
+    }
+}
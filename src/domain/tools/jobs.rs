@@ -0,0 +1,65 @@
+//! systemd job queue MCP handler.
+
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::{
+    AppState,
+    domain::{
+        responses::{generated_at_utc_string, paginate_rows, tool_success_response},
+        utils::normalize_scope,
+    },
+    errors::AppError,
+    mcp::rpc::{app_error_to_json_rpc, json_rpc_invalid_params},
+};
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    scope: Option<String>,
+    limit: Option<u32>,
+}
+
+/// Validates and handles `list_jobs`, returning queued jobs with their waits.
+pub async fn handle(
+    state: &AppState,
+    id: Option<Value>,
+    arguments: Option<serde_json::Map<String, Value>>,
+) -> Value {
+    let params: Params = match serde_json::from_value(json!(arguments.unwrap_or_default())) {
+        Ok(value) => value,
+        Err(_) => return json_rpc_invalid_params(id),
+    };
+    let scope = match normalize_scope(params.scope) {
+        Ok(value) => value,
+        Err(err) => return app_error_to_json_rpc(id, err),
+    };
+    let limit = params.limit.unwrap_or(200);
+    if !(1..=1000).contains(&limit) {
+        return app_error_to_json_rpc(
+            id,
+            AppError::bad_request("invalid_limit", "limit must be between 1 and 1000"),
+        );
+    }
+
+    match state.unit_provider.list_jobs(scope).await {
+        Ok(jobs) => {
+            let page = paginate_rows(jobs, limit as usize);
+            tool_success_response(
+                id,
+                format!("Returned {} of {} queued jobs", page.returned, page.total),
+                serde_json::Map::from_iter([
+                    ("jobs".to_string(), json!(page.rows)),
+                    ("scope".to_string(), json!(scope.as_str())),
+                    ("total".to_string(), json!(page.total)),
+                    ("returned".to_string(), json!(page.returned)),
+                    ("truncated".to_string(), json!(page.truncated)),
+                    (
+                        "generated_at_utc".to_string(),
+                        json!(generated_at_utc_string()),
+                    ),
+                ]),
+            )
+        }
+        Err(err) => app_error_to_json_rpc(id, err),
+    }
+}
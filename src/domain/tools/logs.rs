@@ -5,14 +5,14 @@ use std::collections::{BTreeMap, HashMap};
 
 use crate::domain::responses::{generated_at_utc_string, tool_success_response};
 use crate::domain::utils::{
-    DEFAULT_LOG_LIMIT, MAX_LOG_LIMIT, normalize_priority, normalize_scope, normalize_unit,
-    parse_utc,
+    DEFAULT_LOG_LIMIT, MAX_LOG_LIMIT, normalize_boot_selector, normalize_priority, normalize_scope,
+    normalize_unit, parse_utc,
 };
 use crate::mcp::rpc::{app_error_to_json_rpc, json_rpc_invalid_params};
 use crate::{
     AppState,
     errors::AppError,
-    systemd_client::{LogOrder, LogQuery, UnitScope},
+    systemd_client::{LogOrder, LogQuery},
 };
 
 use super::LogsQueryParams;
@@ -129,7 +129,7 @@ fn build_log_summary(entries: &[crate::systemd_client::JournalLogEntry]) -> LogS
 
 /// Validates the optional log field projection and rejects duplicates.
 fn normalize_fields(fields: Option<Vec<String>>) -> Result<Vec<String>, AppError> {
-    const ALL: [&str; 7] = [
+    const ALL: [&str; 9] = [
         "timestamp_utc",
         "unit",
         "priority",
@@ -137,6 +137,8 @@ fn normalize_fields(fields: Option<Vec<String>>) -> Result<Vec<String>, AppError
         "pid",
         "message",
         "cursor",
+        "owner_uid",
+        "machine",
     ];
     let fields = fields.unwrap_or_else(|| ALL.iter().map(|value| (*value).to_string()).collect());
     if fields.is_empty() {
@@ -314,6 +316,7 @@ pub fn build_log_query(params: LogsQueryParams) -> Result<LogQuery, AppError> {
         end_utc,
         limit: limit as usize,
         cursor: params.cursor.filter(|value| !value.trim().is_empty()),
+        boot_id: None,
     })
 }
 
@@ -342,6 +345,54 @@ fn normalize_logs_query(
     })
 }
 
+/// Replaces a `boot` selector with that boot's time window and returns its boot ID.
+///
+/// The window only positions the journal seek; the `_BOOT_ID` match does the
+/// filtering, so boot windows may exceed the seven-day cap. The newest boot's
+/// window stays open until now.
+async fn resolve_boot_window(
+    state: &AppState,
+    arguments: &mut serde_json::Map<String, Value>,
+    boot: &Value,
+) -> Result<String, AppError> {
+    if arguments.contains_key("start_utc")
+        || arguments.contains_key("end_utc")
+        || arguments.get("since_last_start").and_then(Value::as_bool) == Some(true)
+    {
+        return Err(AppError::bad_request(
+            "invalid_time_range",
+            "boot cannot be combined with start_utc, end_utc, or since_last_start",
+        ));
+    }
+    let selector = normalize_boot_selector(boot)?;
+    let scope = normalize_scope(
+        arguments
+            .get("scope")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+    )?;
+    let boots = state.unit_provider.list_boots(scope).await?;
+    let not_found = || AppError::bad_request("boot_not_found", "journal boot was not found");
+    let record = selector.find(&boots).ok_or_else(not_found)?;
+    let first = parse_utc(&record.first_utc)?.ok_or_else(not_found)?;
+    let last = parse_utc(&record.last_utc)?.ok_or_else(not_found)?;
+    let mut end = last + Duration::milliseconds(1);
+    if record.current {
+        end = end.max(chrono::Utc::now());
+    }
+
+    arguments.insert(
+        "start_utc".to_string(),
+        json!(first.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)),
+    );
+    arguments.insert(
+        "end_utc".to_string(),
+        json!(end.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)),
+    );
+    arguments.insert("allow_large_window".to_string(), json!(true));
+    Ok(record.boot_id.clone())
+}
+
 /// Handles `list_logs` tool execution.
 ///
 /// Parses and validates tool arguments, executes journald query via the provider,
@@ -352,6 +403,13 @@ pub async fn handle_list_logs(
     arguments: Option<serde_json::Map<String, Value>>,
 ) -> Value {
     let mut arguments = arguments.unwrap_or_default();
+    let boot_id = match arguments.remove("boot") {
+        Some(boot) => match resolve_boot_window(state, &mut arguments, &boot).await {
+            Ok(boot_id) => Some(boot_id),
+            Err(err) => return app_error_to_json_rpc(id, err),
+        },
+        None => None,
+    };
     if arguments.get("since_last_start").and_then(Value::as_bool) == Some(true) {
         if arguments.contains_key("start_utc") {
             return app_error_to_json_rpc(
@@ -385,14 +443,13 @@ pub async fn handle_list_logs(
                 .and_then(Value::as_str)
                 .map(str::to_string),
         ) {
-            Ok(UnitScope::System) => UnitScope::System,
-            Ok(UnitScope::User) => UnitScope::User,
+            Ok(scope) if !scope.is_aggregate() => scope,
             _ => {
                 return app_error_to_json_rpc(
                     id,
                     AppError::bad_request(
                         "invalid_scope",
-                        "since_last_start requires one unit and system or user scope",
+                        "since_last_start requires one unit and a single manager scope",
                     ),
                 );
             }
@@ -416,11 +473,12 @@ pub async fn handle_list_logs(
             Err(err) => return app_error_to_json_rpc(id, err),
         }
     }
-    let normalized = match normalize_logs_query(Some(arguments)) {
+    let mut normalized = match normalize_logs_query(Some(arguments)) {
         Ok(value) => value,
         Err(NormalizeLogsError::InvalidParams) => return json_rpc_invalid_params(id),
         Err(NormalizeLogsError::Domain(err)) => return app_error_to_json_rpc(id, err),
     };
+    normalized.query.boot_id = boot_id;
 
     match state
         .unit_provider
@@ -452,7 +510,7 @@ pub async fn handle_list_logs(
                 log_result.entries.len()
             };
             let generated_at_utc = generated_at_utc_string();
-            let window = serde_json::Map::from_iter([
+            let mut window = serde_json::Map::from_iter([
                 (
                     "start_utc".to_string(),
                     json!(
@@ -474,6 +532,9 @@ pub async fn handle_list_logs(
                     ),
                 ),
             ]);
+            if let Some(boot_id) = normalized.query.boot_id.as_deref() {
+                window.insert("boot_id".to_string(), json!(boot_id));
+            }
 
             if normalized.summary_enabled {
                 let summary = build_log_summary(&log_result.entries);
@@ -0,0 +1,78 @@
+//! systemd-machined machine inventory MCP handler.
+
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::{
+    AppState,
+    domain::responses::{generated_at_utc_string, paginate_rows, tool_success_response},
+    errors::AppError,
+    mcp::rpc::{app_error_to_json_rpc, json_rpc_invalid_params},
+    systemd_client::MachineName,
+};
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    name: Option<String>,
+    limit: Option<u32>,
+}
+
+/// Validates an optional machine name using machined's hostname-like alphabet.
+fn normalize_machine_name(name: &str) -> Result<MachineName, AppError> {
+    MachineName::parse(name.trim()).ok_or_else(|| {
+        AppError::bad_request("invalid_machine", "machine must be a valid machine name")
+    })
+}
+
+/// Validates and handles `list_machines`, returning machined registrations.
+pub async fn handle(
+    state: &AppState,
+    id: Option<Value>,
+    arguments: Option<serde_json::Map<String, Value>>,
+) -> Value {
+    let params: Params = match serde_json::from_value(json!(arguments.unwrap_or_default())) {
+        Ok(value) => value,
+        Err(_) => return json_rpc_invalid_params(id),
+    };
+    let name = match params
+        .name
+        .as_deref()
+        .map(normalize_machine_name)
+        .transpose()
+    {
+        Ok(value) => value,
+        Err(err) => return app_error_to_json_rpc(id, err),
+    };
+    let limit = params.limit.unwrap_or(200);
+    if !(1..=1000).contains(&limit) {
+        return app_error_to_json_rpc(
+            id,
+            AppError::bad_request("invalid_limit", "limit must be between 1 and 1000"),
+        );
+    }
+
+    match state
+        .unit_provider
+        .list_machines(name.as_ref().map(MachineName::as_str))
+        .await
+    {
+        Ok(machines) => {
+            let page = paginate_rows(machines, limit as usize);
+            tool_success_response(
+                id,
+                format!("Returned {} of {} machines", page.returned, page.total),
+                serde_json::Map::from_iter([
+                    ("machines".to_string(), json!(page.rows)),
+                    ("total".to_string(), json!(page.total)),
+                    ("returned".to_string(), json!(page.returned)),
+                    ("truncated".to_string(), json!(page.truncated)),
+                    (
+                        "generated_at_utc".to_string(),
+                        json!(generated_at_utc_string()),
+                    ),
+                ]),
+            )
+        }
+        Err(err) => app_error_to_json_rpc(id, err),
+    }
+}
@@ -0,0 +1,623 @@
+//! Service sandboxing exposure analysis MCP handler.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::{
+    AppState,
+    domain::{
+        responses::{generated_at_utc_string, paginate_rows, tool_success_response},
+        utils::{
+            SINGLE_SCOPE_MESSAGE, normalize_scope, normalize_services_limit, normalize_typed_unit,
+        },
+    },
+    errors::AppError,
+    mcp::rpc::{app_error_to_json_rpc, json_rpc_invalid_params},
+    systemd_client::{ServiceSecuritySettings, UnitScope, row_scopes},
+};
+
+const CAP_DAC_OVERRIDE: u32 = 1;
+const CAP_NET_ADMIN: u32 = 12;
+const CAP_SYS_MODULE: u32 = 16;
+const CAP_SYS_PTRACE: u32 = 19;
+const CAP_SYS_ADMIN: u32 = 21;
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    unit: Option<String>,
+    scope: Option<String>,
+    limit: Option<u32>,
+}
+
+/// Outcome of one hardening check; `exposure` is the fraction of its weight that counts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CheckOutcome {
+    Pass,
+    Partial,
+    Exposed,
+    Unknown,
+}
+
+impl CheckOutcome {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Pass => "pass",
+            Self::Partial => "partial",
+            Self::Exposed => "exposed",
+            Self::Unknown => "unknown",
+        }
+    }
+
+    fn exposure(self) -> f64 {
+        match self {
+            Self::Pass | Self::Unknown => 0.0,
+            Self::Partial => 0.5,
+            Self::Exposed => 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct SecurityCheck {
+    id: &'static str,
+    setting: &'static str,
+    description: &'static str,
+    weight: f64,
+    status: &'static str,
+    value: Option<String>,
+    #[serde(skip)]
+    outcome: CheckOutcome,
+}
+
+#[derive(Debug, Serialize)]
+struct SecurityReport {
+    unit: String,
+    scope: String,
+    exposure: f64,
+    rating: &'static str,
+    checks: Vec<SecurityCheck>,
+    findings: Vec<SecurityCheck>,
+}
+
+#[derive(Debug, Serialize)]
+struct SecurityRankingRow {
+    unit: String,
+    scope: String,
+    exposure: f64,
+    rating: &'static str,
+    finding_count: usize,
+}
+
+/// Classifies a boolean protection: enabled passes, disabled is exposed.
+fn flag_outcome(value: Option<bool>) -> (CheckOutcome, Option<String>) {
+    match value {
+        Some(true) => (CheckOutcome::Pass, Some("yes".to_string())),
+        Some(false) => (CheckOutcome::Exposed, Some("no".to_string())),
+        None => (CheckOutcome::Unknown, None),
+    }
+}
+
+/// Classifies an allow/deny list property; an empty deny list means no restriction.
+fn filter_outcome(value: Option<&(bool, Vec<String>)>) -> (CheckOutcome, Option<String>) {
+    match value {
+        Some((false, entries)) if entries.is_empty() => {
+            (CheckOutcome::Exposed, Some("unrestricted".to_string()))
+        }
+        Some((true, entries)) => (
+            CheckOutcome::Pass,
+            Some(format!("allow-list ({} entries)", entries.len())),
+        ),
+        Some((false, entries)) => (
+            CheckOutcome::Partial,
+            Some(format!("deny-list ({} entries)", entries.len())),
+        ),
+        None => (CheckOutcome::Unknown, None),
+    }
+}
+
+/// Evaluates every weighted hardening check against one service's settings.
+///
+/// Weights loosely follow `systemd-analyze security`: identity and privilege
+/// escalation dominate, filesystem and kernel protections follow, and narrow
+/// restrictions carry small weights.
+fn evaluate_checks(settings: &ServiceSecuritySettings) -> Vec<SecurityCheck> {
+    let user = match (settings.dynamic_user, settings.user.as_deref()) {
+        (Some(true), _) => (CheckOutcome::Pass, Some("dynamic".to_string())),
+        (_, Some(user)) if !user.is_empty() && user != "root" && user != "0" => {
+            (CheckOutcome::Pass, Some(user.to_string()))
+        }
+        (None, None) => (CheckOutcome::Unknown, None),
+        _ => (CheckOutcome::Exposed, Some("root".to_string())),
+    };
+    let protect_system = match settings.protect_system.as_deref() {
+        Some("strict") => (CheckOutcome::Pass, Some("strict".to_string())),
+        Some(value @ ("yes" | "full" | "true")) => (CheckOutcome::Partial, Some(value.to_string())),
+        Some(value) => (CheckOutcome::Exposed, Some(value.to_string())),
+        None => (CheckOutcome::Unknown, None),
+    };
+    let protect_home = match settings.protect_home.as_deref() {
+        Some(value @ ("yes" | "true" | "tmpfs")) => (CheckOutcome::Pass, Some(value.to_string())),
+        Some("read-only") => (CheckOutcome::Partial, Some("read-only".to_string())),
+        Some(value) => (CheckOutcome::Exposed, Some(value.to_string())),
+        None => (CheckOutcome::Unknown, None),
+    };
+    let protect_proc = match settings.protect_proc.as_deref() {
+        Some(value @ ("invisible" | "noaccess")) => (CheckOutcome::Pass, Some(value.to_string())),
+        Some("ptraceable") => (CheckOutcome::Partial, Some("ptraceable".to_string())),
+        Some(value) => (CheckOutcome::Exposed, Some(value.to_string())),
+        None => (CheckOutcome::Unknown, None),
+    };
+    let has_capability = |mask: u64, capability: u32| mask & (1_u64 << capability) != 0;
+    let bounding_set = match settings.capability_bounding_set {
+        Some(mask) if has_capability(mask, CAP_SYS_ADMIN) => (
+            CheckOutcome::Exposed,
+            Some("includes CAP_SYS_ADMIN".to_string()),
+        ),
+        Some(mask)
+            if [
+                CAP_DAC_OVERRIDE,
+                CAP_NET_ADMIN,
+                CAP_SYS_MODULE,
+                CAP_SYS_PTRACE,
+            ]
+            .iter()
+            .any(|capability| has_capability(mask, *capability)) =>
+        {
+            (
+                CheckOutcome::Partial,
+                Some("includes privileged capabilities".to_string()),
+            )
+        }
+        Some(_) => (CheckOutcome::Pass, Some("restricted".to_string())),
+        None => (CheckOutcome::Unknown, None),
+    };
+    let ambient = match settings.ambient_capabilities {
+        Some(0) => (CheckOutcome::Pass, Some("none".to_string())),
+        Some(_) => (CheckOutcome::Exposed, Some("granted".to_string())),
+        None => (CheckOutcome::Unknown, None),
+    };
+    let architectures = match settings.system_call_architectures.as_deref() {
+        Some([]) => (CheckOutcome::Exposed, Some("any".to_string())),
+        Some(values) => (CheckOutcome::Pass, Some(values.join(" "))),
+        None => (CheckOutcome::Unknown, None),
+    };
+
+    let rows = [
+        (
+            "user",
+            "User=/DynamicUser=",
+            "Service runs as root",
+            2.0,
+            user,
+        ),
+        (
+            "no_new_privileges",
+            "NoNewPrivileges=",
+            "Processes may gain privileges via setuid or file capabilities",
+            1.0,
+            flag_outcome(settings.no_new_privileges),
+        ),
+        (
+            "capability_bounding_set",
+            "CapabilityBoundingSet=",
+            "Capability bounding set retains privileged capabilities",
+            1.5,
+            bounding_set,
+        ),
+        (
+            "ambient_capabilities",
+            "AmbientCapabilities=",
+            "Service is granted ambient capabilities",
+            0.5,
+            ambient,
+        ),
+        (
+            "system_call_filter",
+            "SystemCallFilter=",
+            "System calls are not filtered",
+            1.0,
+            filter_outcome(settings.system_call_filter.as_ref()),
+        ),
+        (
+            "protect_system",
+            "ProtectSystem=",
+            "OS directories are writable by the service",
+            1.0,
+            protect_system,
+        ),
+        (
+            "protect_home",
+            "ProtectHome=",
+            "Home directories are accessible to the service",
+            1.0,
+            protect_home,
+        ),
+        (
+            "private_tmp",
+            "PrivateTmp=",
+            "Service shares /tmp with the host",
+            0.5,
+            flag_outcome(settings.private_tmp),
+        ),
+        (
+            "private_devices",
+            "PrivateDevices=",
+            "Service can access physical devices",
+            0.5,
+            flag_outcome(settings.private_devices),
+        ),
+        (
+            "private_network",
+            "PrivateNetwork=",
+            "Service shares the host network namespace",
+            0.5,
+            flag_outcome(settings.private_network),
+        ),
+        (
+            "private_users",
+            "PrivateUsers=",
+            "Service shares the host user namespace",
+            0.5,
+            flag_outcome(settings.private_users),
+        ),
+        (
+            "restrict_address_families",
+            "RestrictAddressFamilies=",
+            "Socket address families are not restricted",
+            0.5,
+            filter_outcome(settings.restrict_address_families.as_ref()),
+        ),
+        (
+            "protect_kernel_tunables",
+            "ProtectKernelTunables=",
+            "Kernel tunables in /proc and /sys are writable",
+            0.5,
+            flag_outcome(settings.protect_kernel_tunables),
+        ),
+        (
+            "protect_kernel_modules",
+            "ProtectKernelModules=",
+            "Service may load kernel modules",
+            0.5,
+            flag_outcome(settings.protect_kernel_modules),
+        ),
+        (
+            "protect_control_groups",
+            "ProtectControlGroups=",
+            "Control group hierarchy is writable",
+            0.5,
+            flag_outcome(settings.protect_control_groups),
+        ),
+        (
+            "protect_kernel_logs",
+            "ProtectKernelLogs=",
+            "Service may read or write the kernel log ring buffer",
+            0.3,
+            flag_outcome(settings.protect_kernel_logs),
+        ),
+        (
+            "protect_proc",
+            "ProtectProc=",
+            "Other processes are visible in /proc",
+            0.3,
+            protect_proc,
+        ),
+        (
+            "memory_deny_write_execute",
+            "MemoryDenyWriteExecute=",
+            "Service may create writable and executable memory",
+            0.3,
+            flag_outcome(settings.memory_deny_write_execute),
+        ),
+        (
+            "restrict_suid_sgid",
+            "RestrictSUIDSGID=",
+            "Service may create setuid/setgid files",
+            0.3,
+            flag_outcome(settings.restrict_suid_sgid),
+        ),
+        (
+            "protect_clock",
+            "ProtectClock=",
+            "Service may change the system clock",
+            0.2,
+            flag_outcome(settings.protect_clock),
+        ),
+        (
+            "protect_hostname",
+            "ProtectHostname=",
+            "Service may change the hostname",
+            0.2,
+            flag_outcome(settings.protect_hostname),
+        ),
+        (
+            "restrict_realtime",
+            "RestrictRealtime=",
+            "Service may acquire realtime scheduling",
+            0.2,
+            flag_outcome(settings.restrict_realtime),
+        ),
+        (
+            "lock_personality",
+            "LockPersonality=",
+            "Service may change its execution domain",
+            0.2,
+            flag_outcome(settings.lock_personality),
+        ),
+        (
+            "system_call_architectures",
+            "SystemCallArchitectures=",
+            "Non-native system call ABIs are allowed",
+            0.2,
+            architectures,
+        ),
+    ];
+
+    rows.into_iter()
+        .map(
+            |(id, setting, description, weight, (outcome, value))| SecurityCheck {
+                id,
+                setting,
+                description,
+                weight,
+                status: outcome.as_str(),
+                value,
+                outcome,
+            },
+        )
+        .collect()
+}
+
+/// Scores checks on a 0-10 scale where 10 means fully exposed.
+///
+/// Unknown checks are excluded from both the numerator and the total weight.
+fn exposure_score(checks: &[SecurityCheck]) -> f64 {
+    let (exposed, total) = checks
+        .iter()
+        .filter(|check| check.outcome != CheckOutcome::Unknown)
+        .fold((0.0, 0.0), |(exposed, total), check| {
+            (
+                exposed + check.weight * check.outcome.exposure(),
+                total + check.weight,
+            )
+        });
+    if total == 0.0 {
+        return 10.0;
+    }
+    (exposed / total * 100.0).round() / 10.0
+}
+
+/// Maps an exposure score onto a coarse rating band.
+fn exposure_rating(exposure: f64) -> &'static str {
+    if exposure < 2.0 {
+        "safe"
+    } else if exposure < 5.0 {
+        "medium"
+    } else if exposure < 8.0 {
+        "exposed"
+    } else {
+        "unsafe"
+    }
+}
+
+/// Builds the full report for one service.
+fn build_report(
+    unit: String,
+    scope: UnitScope,
+    settings: &ServiceSecuritySettings,
+) -> SecurityReport {
+    let mut checks = evaluate_checks(settings);
+    let exposure = exposure_score(&checks);
+    checks.sort_by(|left, right| {
+        right
+            .weight
+            .total_cmp(&left.weight)
+            .then_with(|| left.id.cmp(right.id))
+    });
+    let findings = checks
+        .iter()
+        .filter(|check| matches!(check.outcome, CheckOutcome::Exposed | CheckOutcome::Partial))
+        .map(|check| SecurityCheck {
+            value: check.value.clone(),
+            ..*check
+        })
+        .collect();
+    SecurityReport {
+        unit,
+        scope: scope.as_str().to_string(),
+        exposure,
+        rating: exposure_rating(exposure),
+        checks,
+        findings,
+    }
+}
+
+/// Validates and handles `analyze_unit_security` for one service or the whole host.
+pub async fn handle(
+    state: &AppState,
+    id: Option<Value>,
+    arguments: Option<serde_json::Map<String, Value>>,
+) -> Value {
+    let params: Params = match serde_json::from_value(json!(arguments.unwrap_or_default())) {
+        Ok(value) => value,
+        Err(_) => return json_rpc_invalid_params(id),
+    };
+    let scope = match normalize_scope(params.scope) {
+        Ok(value) => value,
+        Err(err) => return app_error_to_json_rpc(id, err),
+    };
+
+    match params.unit {
+        Some(unit) => analyze_single(state, id, unit, scope).await,
+        None => {
+            let limit = match normalize_services_limit(params.limit) {
+                Ok(value) => value,
+                Err(err) => return app_error_to_json_rpc(id, err),
+            };
+            analyze_host(state, id, scope, limit).await
+        }
+    }
+}
+
+/// Reports every check and the findings for one service.
+async fn analyze_single(
+    state: &AppState,
+    id: Option<Value>,
+    unit: String,
+    scope: UnitScope,
+) -> Value {
+    let unit = match normalize_typed_unit(unit) {
+        Ok(value) if value.ends_with(".service") => value,
+        Ok(_) => {
+            return app_error_to_json_rpc(
+                id,
+                AppError::bad_request("invalid_unit", "unit must be a .service unit"),
+            );
+        }
+        Err(err) => return app_error_to_json_rpc(id, err),
+    };
+    if scope.is_aggregate() {
+        return app_error_to_json_rpc(
+            id,
+            AppError::bad_request("invalid_scope", SINGLE_SCOPE_MESSAGE),
+        );
+    }
+    match state
+        .unit_provider
+        .service_security_settings(scope, std::slice::from_ref(&unit))
+        .await
+    {
+        Ok(mut settings) => match settings.remove(&unit) {
+            Some(settings) => {
+                let report = build_report(unit, scope, &settings);
+                let message = format!(
+                    "Service exposure {:.1} ({}) with {} findings",
+                    report.exposure,
+                    report.rating,
+                    report.findings.len()
+                );
+                let mut payload = match serde_json::to_value(report) {
+                    Ok(Value::Object(object)) => object,
+                    _ => {
+                        return app_error_to_json_rpc(
+                            id,
+                            AppError::internal("failed to serialize security report".to_string()),
+                        );
+                    }
+                };
+                payload.insert(
+                    "generated_at_utc".to_string(),
+                    json!(generated_at_utc_string()),
+                );
+                tool_success_response(id, message, payload)
+            }
+            None => app_error_to_json_rpc(
+                id,
+                AppError::bad_request("unit_not_found", "systemd unit was not found"),
+            ),
+        },
+        Err(err) => app_error_to_json_rpc(id, err),
+    }
+}
+
+/// Ranks every loaded service of the scope by exposure, most exposed first.
+async fn analyze_host(
+    state: &AppState,
+    id: Option<Value>,
+    scope: UnitScope,
+    limit: usize,
+) -> Value {
+    let services = match state.unit_provider.list_service_units(scope).await {
+        Ok(rows) => rows,
+        Err(err) => return app_error_to_json_rpc(id, err),
+    };
+
+    let mut rows = Vec::new();
+    for concrete in row_scopes(services.iter().map(|row| row.scope.as_str())) {
+        let units = services
+            .iter()
+            .filter(|row| row.scope == concrete.as_str() && row.load_state == "loaded")
+            .map(|row| row.unit.clone())
+            .collect::<Vec<_>>();
+        if units.is_empty() {
+            continue;
+        }
+        let settings = match state
+            .unit_provider
+            .service_security_settings(concrete, &units)
+            .await
+        {
+            Ok(settings) => settings,
+            Err(err) if scope.is_aggregate() => {
+                tracing::warn!(scope = %concrete.as_str(), error = %err, "failed to analyze service security");
+                continue;
+            }
+            Err(err) => return app_error_to_json_rpc(id, err),
+        };
+        rows.extend(settings.into_iter().map(|(unit, settings)| {
+            let report = build_report(unit, concrete, &settings);
+            SecurityRankingRow {
+                unit: report.unit,
+                scope: report.scope,
+                exposure: report.exposure,
+                rating: report.rating,
+                finding_count: report.findings.len(),
+            }
+        }));
+    }
+
+    rows.sort_by(|left, right| {
+        right
+            .exposure
+            .total_cmp(&left.exposure)
+            .then_with(|| left.unit.cmp(&right.unit))
+            .then_with(|| left.scope.cmp(&right.scope))
+    });
+    let page = paginate_rows(rows, limit);
+
+    tool_success_response(
+        id,
+        format!(
+            "Ranked {} of {} services by exposure",
+            page.returned, page.total
+        ),
+        serde_json::Map::from_iter([
+            ("services".to_string(), json!(page.rows)),
+            ("total".to_string(), json!(page.total)),
+            ("returned".to_string(), json!(page.returned)),
+            ("truncated".to_string(), json!(page.truncated)),
+            (
+                "generated_at_utc".to_string(),
+                json!(generated_at_utc_string()),
+            ),
+        ]),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{evaluate_checks, exposure_rating, exposure_score};
+    use crate::systemd_client::ServiceSecuritySettings;
+
+    #[test]
+    fn excludes_unknown_checks_and_scores_partial_protections() {
+        let settings = ServiceSecuritySettings {
+            user: Some("www-data".to_string()),
+            protect_system: Some("full".to_string()),
+            capability_bounding_set: Some(1 << 12),
+            ..ServiceSecuritySettings::default()
+        };
+        let checks = evaluate_checks(&settings);
+        let unknown = checks
+            .iter()
+            .filter(|check| check.status == "unknown")
+            .count();
+        assert_eq!(unknown, 21);
+
+        // (1.0 * 0.5 + 1.5 * 0.5) / (2.0 + 1.0 + 1.5) * 10 = 2.78
+        let exposure = exposure_score(&checks);
+        assert_eq!(exposure, 2.8);
+        assert_eq!(exposure_rating(exposure), "medium");
+        assert_eq!(exposure_score(&[]), 10.0);
+    }
+}
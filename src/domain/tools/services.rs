@@ -1,16 +1,19 @@
 use serde::Serialize;
 use serde_json::{Value, json};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
+use tracing::warn;
 
 use crate::AppState;
-use crate::domain::responses::{generated_at_utc_string, paginate_rows, tool_success_response};
+use crate::domain::responses::{
+    cache_age_ms, generated_at_utc_string, paginate_rows, tool_success_response,
+};
 use crate::domain::utils::{
     filter_services_by_name_contains, filter_services_by_state, normalize_name_contains,
     normalize_scope, normalize_service_state, normalize_services_limit, sort_services,
 };
 use crate::errors::AppError;
 use crate::mcp::rpc::{app_error_to_json_rpc, json_rpc_invalid_params};
-use crate::systemd_client::UnitScope;
+use crate::systemd_client::{UnitResources, UnitScope, UnitStatus, row_scopes};
 
 use super::ServicesQueryParams;
 
@@ -21,6 +24,7 @@ struct NormalizedServicesQuery {
     name_contains_filter: Option<String>,
     limit: usize,
     summary_enabled: bool,
+    include_resources: bool,
 }
 
 enum NormalizeServicesError {
@@ -39,14 +43,16 @@ struct FailedUnitSummary {
 #[derive(Debug, Serialize)]
 struct ServiceSummary {
     counts_by_active_state: BTreeMap<String, usize>,
+    flapping_count: usize,
     failed_units: Vec<FailedUnitSummary>,
     degraded_hint: Option<String>,
 }
 
 /// Builds `list_services` summary payload for triage mode.
 ///
-/// Includes state counts, a capped failed-unit list, and an optional degraded hint.
-fn build_service_summary(services: &[crate::systemd_client::UnitStatus]) -> ServiceSummary {
+/// Includes state counts, the flapping count, a capped failed-unit list, and an
+/// optional degraded hint.
+fn build_service_summary(services: &[UnitStatus]) -> ServiceSummary {
     let mut counts_by_active_state = BTreeMap::new();
     for service in services {
         *counts_by_active_state
@@ -79,6 +85,7 @@ fn build_service_summary(services: &[crate::systemd_client::UnitStatus]) -> Serv
 
     ServiceSummary {
         counts_by_active_state,
+        flapping_count: services.iter().filter(|service| service.flapping).count(),
         failed_units,
         degraded_hint,
     }
@@ -102,6 +109,7 @@ fn normalize_services_query(
     let limit =
         normalize_services_limit(query_params.limit).map_err(NormalizeServicesError::Domain)?;
     let summary_enabled = query_params.summary.unwrap_or(false);
+    let include_resources = query_params.include_resources.unwrap_or(false);
 
     Ok(NormalizedServicesQuery {
         scope,
@@ -109,9 +117,42 @@ fn normalize_services_query(
         name_contains_filter,
         limit,
         summary_enabled,
+        include_resources,
     })
 }
 
+/// Reads cgroup resources for returned rows, one provider call per row scope.
+///
+/// Resource lookup is best-effort: a failing scope is logged and its rows keep a
+/// `null` resources object rather than failing the listing.
+async fn resources_for_rows(
+    state: &AppState,
+    rows: &[UnitStatus],
+) -> HashMap<(String, String), UnitResources> {
+    let mut resources = HashMap::new();
+    for scope in row_scopes(rows.iter().map(|row| row.scope.as_str())) {
+        let units = rows
+            .iter()
+            .filter(|row| row.scope == scope.as_str())
+            .map(|row| row.unit.clone())
+            .collect::<Vec<_>>();
+        if units.is_empty() {
+            continue;
+        }
+        match state.unit_provider.unit_resources(scope, &units).await {
+            Ok(by_unit) => resources.extend(
+                by_unit
+                    .into_iter()
+                    .map(|(unit, value)| ((scope.as_str().to_string(), unit), value)),
+            ),
+            Err(err) => {
+                warn!(scope = %scope.as_str(), error = %err, "failed to read service resources");
+            }
+        }
+    }
+    resources
+}
+
 /// Handles `list_services` tool execution.
 ///
 /// Parses tool arguments, validates filters/limits, and returns either detailed
@@ -144,6 +185,7 @@ pub async fn handle_list_services(
 
             let page = paginate_rows(services, normalized.limit);
             let generated_at_utc = generated_at_utc_string();
+            let cache_age_ms = cache_age_ms(state.unit_provider.cache_age(normalized.scope));
 
             if normalized.summary_enabled {
                 let summary = build_service_summary(&page.rows);
@@ -157,19 +199,34 @@ pub async fn handle_list_services(
                         ("returned".to_string(), json!(page.returned)),
                         ("truncated".to_string(), json!(page.truncated)),
                         ("generated_at_utc".to_string(), json!(generated_at_utc)),
+                        ("cache_age_ms".to_string(), cache_age_ms),
                     ]),
                 );
             }
 
+            let mut rows = json!(page.rows);
+            if normalized.include_resources {
+                let mut resources = resources_for_rows(state, &page.rows).await;
+                if let Some(rows) = rows.as_array_mut() {
+                    for (row, service) in rows.iter_mut().zip(&page.rows) {
+                        if let Some(object) = row.as_object_mut() {
+                            let key = (service.scope.clone(), service.unit.clone());
+                            object.insert("resources".to_string(), json!(resources.remove(&key)));
+                        }
+                    }
+                }
+            }
+
             tool_success_response(
                 id,
                 format!("Returned {} of {} services", page.returned, page.total),
                 serde_json::Map::from_iter([
-                    ("services".to_string(), json!(page.rows)),
+                    ("services".to_string(), rows),
                     ("total".to_string(), json!(page.total)),
                     ("returned".to_string(), json!(page.returned)),
                     ("truncated".to_string(), json!(page.truncated)),
                     ("generated_at_utc".to_string(), json!(generated_at_utc)),
+                    ("cache_age_ms".to_string(), cache_age_ms),
                 ]),
             )
         }
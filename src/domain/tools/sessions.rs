@@ -0,0 +1,97 @@
+//! Login session inventory MCP handler.
+
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::{
+    AppState,
+    domain::responses::{generated_at_utc_string, paginate_rows, tool_success_response},
+    errors::AppError,
+    mcp::rpc::{app_error_to_json_rpc, json_rpc_invalid_params},
+};
+
+const MAX_USER_NAME_LENGTH: usize = 256;
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    user: Option<String>,
+    limit: Option<u32>,
+}
+
+/// Validates an optional exact user-name filter.
+///
+/// Names are limited to the portable user-name alphabet plus `@` and `$`
+/// used by directory-backed and machine accounts.
+fn normalize_user(user: Option<String>) -> Result<Option<String>, AppError> {
+    let Some(user) = user else {
+        return Ok(None);
+    };
+    let user = user.trim();
+    if user.is_empty()
+        || user.len() > MAX_USER_NAME_LENGTH
+        || !user
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '.' | '_' | '-' | '@' | '$'))
+    {
+        return Err(AppError::bad_request(
+            "invalid_user",
+            "user must be a valid user name",
+        ));
+    }
+    Ok(Some(user.to_string()))
+}
+
+/// Validates and handles `list_sessions`, returning sessions with users and seats.
+pub async fn handle(
+    state: &AppState,
+    id: Option<Value>,
+    arguments: Option<serde_json::Map<String, Value>>,
+) -> Value {
+    let params: Params = match serde_json::from_value(json!(arguments.unwrap_or_default())) {
+        Ok(value) => value,
+        Err(_) => return json_rpc_invalid_params(id),
+    };
+    let user = match normalize_user(params.user) {
+        Ok(value) => value,
+        Err(err) => return app_error_to_json_rpc(id, err),
+    };
+    let limit = params.limit.unwrap_or(200);
+    if !(1..=1000).contains(&limit) {
+        return app_error_to_json_rpc(
+            id,
+            AppError::bad_request("invalid_limit", "limit must be between 1 and 1000"),
+        );
+    }
+
+    match state.unit_provider.list_login_sessions().await {
+        Ok(mut inventory) => {
+            if let Some(user) = user.as_deref() {
+                inventory.sessions.retain(|session| session.user == user);
+                inventory.users.retain(|row| row.user == user);
+            }
+            let page = paginate_rows(inventory.sessions, limit as usize);
+            tool_success_response(
+                id,
+                format!(
+                    "Returned {} of {} login sessions for {} users",
+                    page.returned,
+                    page.total,
+                    inventory.users.len()
+                ),
+                serde_json::Map::from_iter([
+                    ("sessions".to_string(), json!(page.rows)),
+                    ("users".to_string(), json!(inventory.users)),
+                    ("seats".to_string(), json!(inventory.seats)),
+                    ("total".to_string(), json!(page.total)),
+                    ("returned".to_string(), json!(page.returned)),
+                    ("truncated".to_string(), json!(page.truncated)),
+                    (
+                        "generated_at_utc".to_string(),
+                        json!(generated_at_utc_string()),
+                    ),
+                ]),
+            )
+        }
+        Err(err) => app_error_to_json_rpc(id, err),
+    }
+}
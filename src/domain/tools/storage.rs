@@ -0,0 +1,240 @@
+//! Filesystem capacity and journal disk usage MCP handler.
+
+use std::collections::HashSet;
+
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::{
+    AppState,
+    domain::responses::{generated_at_utc_string, paginate_rows, tool_success_response},
+    errors::AppError,
+    mcp::rpc::{app_error_to_json_rpc, json_rpc_invalid_params},
+    systemd_client::{MountUsage, UnitScope},
+};
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    bytes_threshold_percent: Option<u32>,
+    inodes_threshold_percent: Option<u32>,
+    limit: Option<u32>,
+}
+
+/// Validates and handles `get_storage_status`, flagging mounts at or over the
+/// byte and inode thresholds.
+pub async fn handle(
+    state: &AppState,
+    id: Option<Value>,
+    arguments: Option<serde_json::Map<String, Value>>,
+) -> Value {
+    let params: Params = match serde_json::from_value(json!(arguments.unwrap_or_default())) {
+        Ok(value) => value,
+        Err(_) => return json_rpc_invalid_params(id),
+    };
+    let bytes_threshold = match normalize_threshold(
+        params.bytes_threshold_percent,
+        "bytes_threshold_percent must be between 1 and 100",
+    ) {
+        Ok(value) => value,
+        Err(err) => return app_error_to_json_rpc(id, err),
+    };
+    let inodes_threshold = match normalize_threshold(
+        params.inodes_threshold_percent,
+        "inodes_threshold_percent must be between 1 and 100",
+    ) {
+        Ok(value) => value,
+        Err(err) => return app_error_to_json_rpc(id, err),
+    };
+    let limit = params.limit.unwrap_or(200);
+    if !(1..=1000).contains(&limit) {
+        return app_error_to_json_rpc(
+            id,
+            AppError::bad_request("invalid_limit", "limit must be between 1 and 1000"),
+        );
+    }
+
+    let report = match state.unit_provider.storage_status().await {
+        Ok(report) => report,
+        Err(err) => return app_error_to_json_rpc(id, err),
+    };
+    let mount_units = match state
+        .unit_provider
+        .list_units(UnitScope::System, Some("mount"))
+        .await
+    {
+        Ok(units) => units
+            .into_iter()
+            .map(|unit| unit.unit)
+            .collect::<HashSet<_>>(),
+        Err(err) => {
+            tracing::warn!(error = %err, "failed to list mount units");
+            HashSet::new()
+        }
+    };
+
+    let mut mounts = report.mounts;
+    mounts.sort_by(|left, right| left.mount_point.cmp(&right.mount_point));
+    let mut over_threshold = Vec::new();
+    let rows = mounts
+        .into_iter()
+        .map(|mount| {
+            let bytes_used_percent = bytes_used_percent(&mount);
+            let inodes_used_percent = percent(mount.inodes_used, mount.inodes_total);
+            let over_bytes =
+                bytes_used_percent.is_some_and(|value| value >= f64::from(bytes_threshold));
+            let over_inodes =
+                inodes_used_percent.is_some_and(|value| value >= f64::from(inodes_threshold));
+            if over_bytes || over_inodes {
+                over_threshold.push(mount.mount_point.clone());
+            }
+            let unit = mount_unit_name(&mount.mount_point);
+            json!({
+                "mount_point": mount.mount_point,
+                "mount_unit": mount_units.contains(&unit).then_some(unit),
+                "source": mount.source,
+                "fs_type": mount.fs_type,
+                "read_only": mount.read_only,
+                "size_bytes": mount.size_bytes,
+                "used_bytes": mount.used_bytes,
+                "available_bytes": mount.available_bytes,
+                "bytes_used_percent": bytes_used_percent,
+                "inodes_total": mount.inodes_total,
+                "inodes_used": mount.inodes_used,
+                "inodes_free": mount.inodes_free,
+                "inodes_used_percent": inodes_used_percent,
+                "over_bytes_threshold": over_bytes,
+                "over_inodes_threshold": over_inodes,
+                "stat_timed_out": mount.stat_timed_out,
+            })
+        })
+        .collect::<Vec<_>>();
+    let page = paginate_rows(rows, limit as usize);
+    let journal_bytes = report
+        .journal_directories
+        .iter()
+        .map(|directory| directory.bytes)
+        .fold(0_u64, u64::saturating_add);
+
+    tool_success_response(
+        id,
+        format!(
+            "Returned {} mounts ({} over threshold)",
+            page.returned,
+            over_threshold.len()
+        ),
+        serde_json::Map::from_iter([
+            ("mounts".to_string(), json!(page.rows)),
+            ("returned".to_string(), json!(page.returned)),
+            ("truncated".to_string(), json!(page.truncated)),
+            ("over_threshold".to_string(), json!(over_threshold)),
+            (
+                "thresholds".to_string(),
+                json!({
+                    "bytes_percent": bytes_threshold,
+                    "inodes_percent": inodes_threshold,
+                }),
+            ),
+            (
+                "journal".to_string(),
+                json!({
+                    "total_bytes": journal_bytes,
+                    "directories": report.journal_directories,
+                }),
+            ),
+            (
+                "generated_at_utc".to_string(),
+                json!(generated_at_utc_string()),
+            ),
+        ]),
+    )
+}
+
+/// Validates a usage threshold percentage; missing values default to 90.
+fn normalize_threshold(value: Option<u32>, message: &'static str) -> Result<u32, AppError> {
+    let value = value.unwrap_or(90);
+    if !(1..=100).contains(&value) {
+        return Err(AppError::bad_request("invalid_threshold", message));
+    }
+    Ok(value)
+}
+
+/// Share of space in use as `df` reports it: used over used plus available,
+/// excluding blocks reserved for privileged users.
+fn bytes_used_percent(mount: &MountUsage) -> Option<f64> {
+    percent(
+        mount.used_bytes,
+        mount.used_bytes.saturating_add(mount.available_bytes),
+    )
+}
+
+/// Rounds `part / whole` to a percentage with one decimal, or `None` for an empty whole.
+fn percent(part: u64, whole: u64) -> Option<f64> {
+    (whole > 0).then(|| (part as f64 / whole as f64 * 1_000.0).round() / 10.0)
+}
+
+/// Returns the `.mount` unit name systemd derives from a mount point path.
+///
+/// Follows `systemd-escape --path --suffix=mount`: slashes are trimmed and
+/// collapsed, inner slashes become `-`, and bytes outside `[A-Za-z0-9:_.]` (and
+/// a leading `.`) become `\xNN`. The root directory is `-.mount`.
+fn mount_unit_name(mount_point: &str) -> String {
+    let segments = mount_point
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>();
+    if segments.is_empty() {
+        return "-.mount".to_string();
+    }
+    let mut name = String::new();
+    for (index, byte) in segments.join("/").bytes().enumerate() {
+        match byte {
+            b'/' => name.push('-'),
+            b'.' if index == 0 => name.push_str("\\x2e"),
+            byte if byte.is_ascii_alphanumeric() || matches!(byte, b':' | b'_' | b'.') => {
+                name.push(char::from(byte));
+            }
+            byte => name.push_str(&format!("\\x{byte:02x}")),
+        }
+    }
+    name.push_str(".mount");
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derives_mount_unit_names_like_systemd_escape() {
+        assert_eq!(mount_unit_name("/"), "-.mount");
+        assert_eq!(mount_unit_name("/var/lib/docker"), "var-lib-docker.mount");
+        assert_eq!(mount_unit_name("//srv//data/"), "srv-data.mount");
+        assert_eq!(
+            mount_unit_name("/mnt/backup disk"),
+            "mnt-backup\\x20disk.mount"
+        );
+        assert_eq!(mount_unit_name("/mnt/a-b"), "mnt-a\\x2db.mount");
+        assert_eq!(mount_unit_name("/.snapshots"), "\\x2esnapshots.mount");
+    }
+
+    #[test]
+    fn computes_df_style_percentages() {
+        let mount = MountUsage {
+            mount_point: "/".to_string(),
+            source: None,
+            fs_type: "ext4".to_string(),
+            read_only: false,
+            size_bytes: 1_000,
+            used_bytes: 450,
+            available_bytes: 50,
+            inodes_total: 0,
+            inodes_used: 0,
+            inodes_free: 0,
+            stat_timed_out: false,
+        };
+
+        assert_eq!(bytes_used_percent(&mount), Some(90.0));
+        assert_eq!(percent(1, 3), Some(33.3));
+        assert_eq!(percent(0, 0), None);
+    }
+}
@@ -0,0 +1,279 @@
+//! Timer run history MCP handler.
+
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::{
+    AppState,
+    domain::{
+        responses::{generated_at_utc_string, paginate_rows, tool_success_response},
+        utils::{SINGLE_SCOPE_MESSAGE, normalize_scope, normalize_typed_unit, parse_utc},
+    },
+    errors::AppError,
+    mcp::rpc::{app_error_to_json_rpc, json_rpc_invalid_params},
+    systemd_client::{UnitRun, UnitRunHistory},
+};
+
+/// Default look-back when `start_utc` is omitted.
+const DEFAULT_WINDOW: Duration = Duration::days(7);
+/// Largest accepted history window.
+const MAX_WINDOW: Duration = Duration::days(31);
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    unit: String,
+    scope: Option<String>,
+    start_utc: Option<String>,
+    end_utc: Option<String>,
+    limit: Option<u32>,
+}
+
+/// Aggregate outcome statistics over the runs of a window.
+///
+/// `success_ratio` and the durations cover finished runs only; `p95_duration_ms`
+/// uses the nearest-rank method.
+#[derive(Debug, Serialize, PartialEq)]
+pub(crate) struct RunSummary {
+    pub total: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub running: usize,
+    pub success_ratio: Option<f64>,
+    pub avg_duration_ms: Option<u64>,
+    pub p95_duration_ms: Option<u64>,
+    pub last_failure: Option<UnitRun>,
+}
+
+/// Summarizes runs given oldest first.
+pub(crate) fn summarize_runs(runs: &[UnitRun]) -> RunSummary {
+    let count = |outcome: &str| runs.iter().filter(|run| run.outcome == outcome).count();
+    let (succeeded, failed, running) = (count("success"), count("failed"), count("running"));
+    let mut durations = runs
+        .iter()
+        .filter(|run| run.outcome != "running")
+        .filter_map(|run| run.duration_ms)
+        .collect::<Vec<_>>();
+    durations.sort_unstable();
+    let finished = succeeded + failed;
+    RunSummary {
+        total: runs.len(),
+        succeeded,
+        failed,
+        running,
+        success_ratio: (finished > 0)
+            .then(|| (succeeded as f64 / finished as f64 * 1_000.0).round() / 1_000.0),
+        avg_duration_ms: (!durations.is_empty())
+            .then(|| durations.iter().sum::<u64>() / durations.len() as u64),
+        p95_duration_ms: (!durations.is_empty())
+            .then(|| durations[(durations.len() * 95).div_ceil(100) - 1]),
+        last_failure: runs
+            .iter()
+            .rev()
+            .find(|run| run.outcome == "failed")
+            .cloned(),
+    }
+}
+
+/// Validates and handles `get_timer_history`, correlating a timer's trigger
+/// unit runs in a window.
+pub async fn handle(
+    state: &AppState,
+    id: Option<Value>,
+    arguments: Option<serde_json::Map<String, Value>>,
+) -> Value {
+    let params: Params = match serde_json::from_value(json!(arguments.unwrap_or_default())) {
+        Ok(value) => value,
+        Err(_) => return json_rpc_invalid_params(id),
+    };
+    let unit = match normalize_typed_unit(params.unit) {
+        Ok(value) if value.ends_with(".timer") => value,
+        Ok(_) => {
+            return app_error_to_json_rpc(
+                id,
+                AppError::bad_request("invalid_unit", "unit must be a .timer unit"),
+            );
+        }
+        Err(err) => return app_error_to_json_rpc(id, err),
+    };
+    let scope = match normalize_scope(params.scope) {
+        Ok(scope) if !scope.is_aggregate() => scope,
+        _ => {
+            return app_error_to_json_rpc(
+                id,
+                AppError::bad_request("invalid_scope", SINGLE_SCOPE_MESSAGE),
+            );
+        }
+    };
+    let (start_utc, end_utc) = match normalize_window(&params.start_utc, &params.end_utc) {
+        Ok(window) => window,
+        Err(err) => return app_error_to_json_rpc(id, err),
+    };
+    let limit = params.limit.unwrap_or(50);
+    if !(1..=200).contains(&limit) {
+        return app_error_to_json_rpc(
+            id,
+            AppError::bad_request("invalid_limit", "limit must be between 1 and 200"),
+        );
+    }
+
+    let timers = match state.unit_provider.list_timer_units(scope).await {
+        Ok(timers) => timers,
+        Err(err) => return app_error_to_json_rpc(id, err),
+    };
+    let Some(timer) = timers.into_iter().find(|timer| timer.unit == unit) else {
+        return app_error_to_json_rpc(
+            id,
+            AppError::bad_request("unit_not_found", "systemd unit was not found"),
+        );
+    };
+    let UnitRunHistory {
+        runs,
+        truncated: history_truncated,
+    } = match timer.trigger_unit.as_deref() {
+        Some(trigger_unit) => match state
+            .unit_provider
+            .list_unit_runs(trigger_unit, scope, start_utc, end_utc)
+            .await
+        {
+            Ok(history) => history,
+            Err(err) => return app_error_to_json_rpc(id, err),
+        },
+        None => UnitRunHistory {
+            runs: Vec::new(),
+            truncated: false,
+        },
+    };
+
+    let summary = summarize_runs(&runs);
+    let page = paginate_rows(runs.into_iter().rev().collect(), limit as usize);
+    let format = |at: DateTime<Utc>| at.to_rfc3339_opts(SecondsFormat::Millis, true);
+    tool_success_response(
+        id,
+        format!(
+            "Found {} runs of {} ({} failed)",
+            summary.total,
+            timer.trigger_unit.as_deref().unwrap_or("no trigger unit"),
+            summary.failed
+        ),
+        serde_json::Map::from_iter([
+            ("unit".to_string(), json!(timer.unit)),
+            ("scope".to_string(), json!(timer.scope)),
+            ("trigger_unit".to_string(), json!(timer.trigger_unit)),
+            ("start_utc".to_string(), json!(format(start_utc))),
+            ("end_utc".to_string(), json!(format(end_utc))),
+            ("summary".to_string(), json!(summary)),
+            ("runs".to_string(), json!(page.rows)),
+            ("returned".to_string(), json!(page.returned)),
+            ("truncated".to_string(), json!(page.truncated)),
+            ("history_truncated".to_string(), json!(history_truncated)),
+            (
+                "generated_at_utc".to_string(),
+                json!(generated_at_utc_string()),
+            ),
+        ]),
+    )
+}
+
+/// Resolves the history window, defaulting to the 7 days before `end_utc` or now.
+fn normalize_window(
+    start_utc: &Option<String>,
+    end_utc: &Option<String>,
+) -> Result<(DateTime<Utc>, DateTime<Utc>), AppError> {
+    let end = parse_utc(end_utc)?.unwrap_or_else(Utc::now);
+    let start = parse_utc(start_utc)?.unwrap_or(end - DEFAULT_WINDOW);
+    if start >= end {
+        return Err(AppError::bad_request(
+            "invalid_time_range",
+            "start_utc must be strictly less than end_utc",
+        ));
+    }
+    if end - start > MAX_WINDOW {
+        return Err(AppError::bad_request(
+            "time_range_too_large",
+            "time window must not exceed 31 days",
+        ));
+    }
+    Ok((start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(outcome: &str, duration_ms: Option<u64>) -> UnitRun {
+        UnitRun {
+            started_utc: "2026-03-01T00:00:00.000Z".to_string(),
+            finished_utc: duration_ms.map(|_| "2026-03-01T00:01:00.000Z".to_string()),
+            duration_ms,
+            outcome: outcome.to_string(),
+            result: None,
+            exit_code: None,
+            exit_status: None,
+        }
+    }
+
+    #[test]
+    fn summarizes_ratio_durations_and_last_failure() {
+        let mut runs = (1..=18)
+            .map(|index| run("success", Some(index * 1_000)))
+            .collect::<Vec<_>>();
+        runs.push(run("failed", Some(100_000)));
+        runs.push(UnitRun {
+            started_utc: "2026-03-02T00:00:00.000Z".to_string(),
+            ..run("failed", Some(50_000))
+        });
+        runs.push(run("running", None));
+
+        let summary = summarize_runs(&runs);
+
+        assert_eq!(summary.total, 21);
+        assert_eq!(
+            (summary.succeeded, summary.failed, summary.running),
+            (18, 2, 1)
+        );
+        assert_eq!(summary.success_ratio, Some(0.9));
+        assert_eq!(summary.avg_duration_ms, Some(16_050));
+        assert_eq!(summary.p95_duration_ms, Some(50_000));
+        assert_eq!(
+            summary.last_failure.map(|run| run.started_utc),
+            Some("2026-03-02T00:00:00.000Z".to_string())
+        );
+    }
+
+    #[test]
+    fn summary_of_no_runs_has_no_ratio_or_durations() {
+        let summary = summarize_runs(&[]);
+
+        assert_eq!(summary.success_ratio, None);
+        assert_eq!(summary.avg_duration_ms, None);
+        assert_eq!(summary.p95_duration_ms, None);
+        assert_eq!(summary.last_failure, None);
+    }
+
+    #[test]
+    fn window_defaults_and_bounds() {
+        let end = Some("2026-03-10T00:00:00Z".to_string());
+        let (start, _) = normalize_window(&None, &end).expect("default window");
+        assert_eq!(
+            start.to_rfc3339_opts(SecondsFormat::Secs, true),
+            "2026-03-03T00:00:00Z"
+        );
+
+        let start = Some("2026-01-01T00:00:00Z".to_string());
+        assert!(matches!(
+            normalize_window(&start, &end),
+            Err(AppError::BadRequest {
+                code: "time_range_too_large",
+                ..
+            })
+        ));
+        assert!(matches!(
+            normalize_window(&end, &end),
+            Err(AppError::BadRequest {
+                code: "invalid_time_range",
+                ..
+            })
+        ));
+    }
+}
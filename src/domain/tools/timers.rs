@@ -1,18 +1,27 @@
-use chrono::{DateTime, Duration, Utc};
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
 use serde::Serialize;
 use serde_json::{Value, json};
 use std::cmp::Ordering;
 use std::collections::BTreeMap;
 
-use crate::domain::responses::{generated_at_utc_string, paginate_rows, tool_success_response};
+use crate::calendar::{CalendarSpec, merged_elapses, time_zone_name};
+use crate::domain::responses::{
+    cache_age_ms, generated_at_utc_string, paginate_rows, tool_success_response,
+};
 use crate::domain::utils::{
     normalize_name_contains, normalize_scope, normalize_timer_state, normalize_timers_limit,
     normalize_timers_order, normalize_timers_sort,
 };
 use crate::mcp::rpc::app_error_to_json_rpc;
-use crate::systemd_client::UnitScope;
+use crate::systemd_client::{TimerCalendarSpec, TimerMonotonicSpec, TimerStatus, UnitScope};
+use crate::time_zone::TimeZone;
 use crate::{AppState, errors::AppError};
 
+/// Base grace after a due time before a timer counts as overdue.
+const OVERDUE_GRACE: Duration = Duration::minutes(5);
+/// Upper bound on missed elapses counted per timer.
+const MAX_MISSED_RUNS: usize = 1000;
+
 #[derive(Debug)]
 pub struct TimersQueryParams {
     pub scope: Option<String>,
@@ -54,10 +63,25 @@ pub struct TimerItem {
     pub result: Option<String>,
     pub load_state: Option<String>,
     pub unit_file_state: Option<String>,
+    pub calendar: Vec<TimerCalendarSpec>,
+    pub monotonic: Vec<TimerMonotonicSpec>,
+    pub accuracy_usec: Option<u64>,
+    pub randomized_delay_usec: Option<u64>,
     pub overdue: bool,
     pub overdue_reason: Option<String>,
 }
 
+/// Calendar elapses that passed since the last trigger without the timer firing.
+///
+/// `capped` is true when counting stopped at the per-timer bound.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct MissedRuns {
+    pub count: usize,
+    pub capped: bool,
+    pub first_missed_utc: Option<String>,
+    pub last_missed_utc: Option<String>,
+}
+
 #[derive(Debug, Serialize)]
 struct NextDueSoonTimer {
     unit: String,
@@ -204,21 +228,89 @@ fn normalize_timers_query(
 ///
 /// Returns `None` for absent or unparsable values. This is used for best-effort
 /// enrichment on timer metadata where partial data is acceptable.
-fn parse_rfc3339_utc(value: Option<&str>) -> Option<DateTime<Utc>> {
+pub(crate) fn parse_rfc3339_utc(value: Option<&str>) -> Option<DateTime<Utc>> {
     value
         .and_then(|timestamp| DateTime::parse_from_rfc3339(timestamp).ok())
         .map(|value| value.with_timezone(&Utc))
 }
 
+/// Returns how long after a due time a timer may still fire on schedule.
+///
+/// systemd may coalesce an elapse by up to `AccuracySec=` and delay it by up
+/// to `RandomizedDelaySec=`; both extend the fixed 5 minute grace.
+pub(crate) fn overdue_grace(timer: &TimerStatus) -> Duration {
+    let delay_usec = timer
+        .accuracy_usec
+        .unwrap_or(0)
+        .saturating_add(timer.randomized_delay_usec.unwrap_or(0));
+    OVERDUE_GRACE + Duration::microseconds(i64::try_from(delay_usec).unwrap_or(i64::MAX / 2))
+}
+
+/// Parses one `OnCalendar=` expression, loading the time zone it names.
+///
+/// Zones come from the process-wide cache, so the zoneinfo database is read
+/// at most once per zone.
+pub(crate) fn parse_calendar_expression(expression: &str) -> Result<CalendarSpec, &'static str> {
+    let time_zone = time_zone_name(expression)
+        .map(TimeZone::load)
+        .transpose()
+        .map_err(|_| "unknown time zone")?;
+    CalendarSpec::parse(expression, time_zone)
+}
+
+/// Parses the timer's `OnCalendar=` expressions, skipping unparsable ones.
+pub(crate) fn parse_calendar_specs(timer: &TimerStatus) -> Vec<CalendarSpec> {
+    timer
+        .calendar
+        .iter()
+        .filter_map(|spec| parse_calendar_expression(&spec.expression).ok())
+        .collect()
+}
+
+/// Counts calendar elapses after `last_run_utc` that are past their grace.
+///
+/// Returns `None` without a last trigger or without parsable calendar specs.
+pub(crate) fn missed_runs(
+    timer: &TimerStatus,
+    specs: &[CalendarSpec],
+    now: DateTime<Utc>,
+    local_zone: &TimeZone,
+) -> Option<MissedRuns> {
+    let last_run = parse_rfc3339_utc(timer.last_run_utc.as_deref())?;
+    if specs.is_empty() {
+        return None;
+    }
+    let mut elapses = merged_elapses(
+        specs,
+        last_run,
+        Some(now - overdue_grace(timer)),
+        MAX_MISSED_RUNS + 1,
+        local_zone,
+    );
+    let capped = elapses.len() > MAX_MISSED_RUNS;
+    elapses.truncate(MAX_MISSED_RUNS);
+    let format = |at: &DateTime<Utc>| at.to_rfc3339_opts(SecondsFormat::Millis, true);
+    Some(MissedRuns {
+        count: elapses.len(),
+        capped,
+        first_missed_utc: elapses.first().map(|(at, _)| format(at)),
+        last_missed_utc: elapses.last().map(|(at, _)| format(at)),
+    })
+}
+
 /// Computes overdue classification for a timer using the project rules.
 ///
 /// Rule summary:
 /// - Timer must be `active`.
 /// - Timer must have a known `next_run_utc`.
-/// - `now` must be strictly later than `next_run_utc + 5 minutes`.
-fn timer_overdue_status(
+/// - `now` must be strictly later than `next_run_utc` plus the grace, or a
+///   calendar elapse since `last_run_utc` must have passed its grace unfired.
+///
+/// The grace is 5 minutes plus the timer's accuracy and randomized delay.
+pub(crate) fn timer_overdue_status(
     now: DateTime<Utc>,
-    timer: &crate::systemd_client::TimerStatus,
+    timer: &TimerStatus,
+    local_zone: &TimeZone,
 ) -> (bool, Option<String>) {
     if !timer.active_state.eq_ignore_ascii_case("active") {
         return (false, Some("not_active".to_string()));
@@ -228,26 +320,40 @@ fn timer_overdue_status(
         return (false, Some("no_next_run_known".to_string()));
     };
 
-    let grace_deadline = next_run + Duration::minutes(5);
-    if now > grace_deadline {
+    if now > next_run + overdue_grace(timer) {
         return (true, Some("past_due_beyond_grace".to_string()));
     }
 
+    // Only the first unfired elapse matters here; `explain_timer` counts them all.
+    if let Some(last_run) = parse_rfc3339_utc(timer.last_run_utc.as_deref())
+        && !merged_elapses(
+            &parse_calendar_specs(timer),
+            last_run,
+            Some(now - overdue_grace(timer)),
+            1,
+            local_zone,
+        )
+        .is_empty()
+    {
+        return (true, Some("missed_scheduled_runs".to_string()));
+    }
+
     (false, None)
 }
 
 /// Builds the API-facing timer item with derived fields.
 fn build_timer_item(
-    timer: crate::systemd_client::TimerStatus,
+    timer: TimerStatus,
     now: DateTime<Utc>,
     include_persistent: bool,
+    local_zone: &TimeZone,
 ) -> TimerItem {
     let next_run = parse_rfc3339_utc(timer.next_run_utc.as_deref());
     let last_run = parse_rfc3339_utc(timer.last_run_utc.as_deref());
 
     let time_until_next_sec = next_run.map(|next| (next - now).num_seconds());
     let time_since_last_sec = last_run.map(|last| (now - last).num_seconds());
-    let (overdue, overdue_reason) = timer_overdue_status(now, &timer);
+    let (overdue, overdue_reason) = timer_overdue_status(now, &timer, local_zone);
 
     TimerItem {
         unit: timer.unit,
@@ -267,6 +373,10 @@ fn build_timer_item(
         result: timer.result,
         load_state: Some(timer.load_state),
         unit_file_state: timer.unit_file_state,
+        calendar: timer.calendar,
+        monotonic: timer.monotonic,
+        accuracy_usec: timer.accuracy_usec,
+        randomized_delay_usec: timer.randomized_delay_usec,
         overdue,
         overdue_reason,
     }
@@ -414,6 +524,9 @@ pub async fn handle_list_timers(
     match state.unit_provider.list_timer_units(normalized.scope).await {
         Ok(timers) => {
             let now = Utc::now();
+            let local_zone = tokio::task::spawn_blocking(TimeZone::host_local)
+                .await
+                .unwrap_or_else(|_| TimeZone::utc());
             let mut timers = timers
                 .into_iter()
                 .filter(|timer| {
@@ -430,7 +543,9 @@ pub async fn handle_list_timers(
                         .map(|needle| timer.unit.to_ascii_lowercase().contains(needle))
                         .unwrap_or(true)
                 })
-                .map(|timer| build_timer_item(timer, now, normalized.include_persistent))
+                .map(|timer| {
+                    build_timer_item(timer, now, normalized.include_persistent, &local_zone)
+                })
                 .collect::<Vec<_>>();
 
             if normalized.overdue_only {
@@ -441,6 +556,7 @@ pub async fn handle_list_timers(
 
             let page = paginate_rows(timers, normalized.limit);
             let generated_at_utc = generated_at_utc_string();
+            let cache_age_ms = cache_age_ms(state.unit_provider.cache_age(normalized.scope));
 
             if normalized.summary_enabled {
                 let summary = build_timer_summary(&page.rows);
@@ -454,6 +570,7 @@ pub async fn handle_list_timers(
                         ("returned".to_string(), json!(page.returned)),
                         ("truncated".to_string(), json!(page.truncated)),
                         ("generated_at_utc".to_string(), json!(generated_at_utc)),
+                        ("cache_age_ms".to_string(), cache_age_ms),
                     ]),
                 );
             }
@@ -467,9 +584,112 @@ pub async fn handle_list_timers(
                     ("returned".to_string(), json!(page.returned)),
                     ("truncated".to_string(), json!(page.truncated)),
                     ("generated_at_utc".to_string(), json!(generated_at_utc)),
+                    ("cache_age_ms".to_string(), cache_age_ms),
                 ]),
             )
         }
         Err(err) => app_error_to_json_rpc(id, err),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hourly_timer(last_run_utc: &str, next_run_utc: &str) -> TimerStatus {
+        TimerStatus {
+            unit: "hourly.timer".to_string(),
+            scope: "system".to_string(),
+            load_state: "loaded".to_string(),
+            active_state: "active".to_string(),
+            sub_state: "waiting".to_string(),
+            unit_file_state: Some("enabled".to_string()),
+            next_run_utc: Some(next_run_utc.to_string()),
+            last_run_utc: Some(last_run_utc.to_string()),
+            trigger_unit: Some("hourly.service".to_string()),
+            persistent: Some(false),
+            result: Some("success".to_string()),
+            calendar: vec![TimerCalendarSpec {
+                base: "OnCalendar".to_string(),
+                expression: "*-*-* *:00:00 UTC".to_string(),
+                next_elapse_utc: Some(next_run_utc.to_string()),
+            }],
+            monotonic: Vec::new(),
+            accuracy_usec: Some(60_000_000),
+            randomized_delay_usec: Some(0),
+        }
+    }
+
+    fn at(value: &str) -> DateTime<Utc> {
+        parse_rfc3339_utc(Some(value)).expect("valid timestamp")
+    }
+
+    #[test]
+    fn missed_runs_counts_elapses_past_grace_since_last_run() {
+        let timer = hourly_timer("2026-03-01T08:00:00.000Z", "2026-03-01T13:00:00.000Z");
+        let specs = parse_calendar_specs(&timer);
+        let missed = missed_runs(
+            &timer,
+            &specs,
+            at("2026-03-01T12:03:00.000Z"),
+            &TimeZone::utc(),
+        )
+        .expect("calendar timer with last run");
+
+        // 12:00 is still within 5 minutes plus one minute accuracy.
+        assert_eq!(missed.count, 3);
+        assert!(!missed.capped);
+        assert_eq!(
+            missed.first_missed_utc.as_deref(),
+            Some("2026-03-01T09:00:00.000Z")
+        );
+        assert_eq!(
+            missed.last_missed_utc.as_deref(),
+            Some("2026-03-01T11:00:00.000Z")
+        );
+    }
+
+    #[test]
+    fn missed_runs_requires_last_run_and_parsable_calendar() {
+        let mut timer = hourly_timer("2026-03-01T08:00:00.000Z", "2026-03-01T13:00:00.000Z");
+        let now = at("2026-03-01T12:03:00.000Z");
+        timer.calendar[0].expression = "not a calendar".to_string();
+        assert_eq!(
+            missed_runs(&timer, &parse_calendar_specs(&timer), now, &TimeZone::utc()),
+            None
+        );
+
+        let mut timer = hourly_timer("2026-03-01T08:00:00.000Z", "2026-03-01T13:00:00.000Z");
+        timer.last_run_utc = None;
+        assert_eq!(
+            missed_runs(&timer, &parse_calendar_specs(&timer), now, &TimeZone::utc()),
+            None
+        );
+    }
+
+    #[test]
+    fn overdue_status_applies_accuracy_and_randomized_delay_to_grace() {
+        let mut timer = hourly_timer("2026-03-01T11:00:00.000Z", "2026-03-01T12:00:00.000Z");
+        timer.randomized_delay_usec = Some(600_000_000);
+        let zone = TimeZone::utc();
+
+        assert_eq!(
+            timer_overdue_status(at("2026-03-01T12:15:00.000Z"), &timer, &zone),
+            (false, None)
+        );
+        assert_eq!(
+            timer_overdue_status(at("2026-03-01T12:16:01.000Z"), &timer, &zone),
+            (true, Some("past_due_beyond_grace".to_string()))
+        );
+    }
+
+    #[test]
+    fn overdue_status_flags_missed_runs_when_next_run_moved_ahead() {
+        let timer = hourly_timer("2026-03-01T08:00:00.000Z", "2026-03-01T13:00:00.000Z");
+
+        assert_eq!(
+            timer_overdue_status(at("2026-03-01T12:03:00.000Z"), &timer, &TimeZone::utc()),
+            (true, Some("missed_scheduled_runs".to_string()))
+        );
+    }
+}
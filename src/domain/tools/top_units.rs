@@ -0,0 +1,247 @@
+//! Top-N cgroup resource consumer ranking MCP handler.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    AppState,
+    domain::{
+        responses::{generated_at_utc_string, tool_success_response},
+        utils::normalize_scope,
+    },
+    errors::AppError,
+    mcp::rpc::{app_error_to_json_rpc, json_rpc_invalid_params},
+    systemd_client::ResourceSample,
+};
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    scope: Option<String>,
+    metric: Option<String>,
+    limit: Option<u32>,
+    interval_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RankMetric {
+    Memory,
+    Cpu,
+    Tasks,
+}
+
+impl RankMetric {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Memory => "memory",
+            Self::Cpu => "cpu",
+            Self::Tasks => "tasks",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct TopUnitRow {
+    unit: String,
+    scope: String,
+    memory_current_bytes: Option<u64>,
+    cpu_percent: Option<f64>,
+    tasks_current: Option<u64>,
+}
+
+impl TopUnitRow {
+    /// Returns the ranking value for `metric`; memory and tasks widen losslessly enough for ordering.
+    fn metric_value(&self, metric: RankMetric) -> Option<f64> {
+        match metric {
+            RankMetric::Memory => self.memory_current_bytes.map(|value| value as f64),
+            RankMetric::Cpu => self.cpu_percent,
+            RankMetric::Tasks => self.tasks_current.map(|value| value as f64),
+        }
+    }
+}
+
+/// Joins two samples into rows, computing CPU percent of one core over `elapsed`.
+///
+/// Memory and task values come from the second sample. Units missing from the first
+/// sample, or whose CPU counter went backwards, get a `null` CPU rate.
+fn build_rows(
+    first: Vec<ResourceSample>,
+    second: Vec<ResourceSample>,
+    elapsed: Duration,
+) -> Vec<TopUnitRow> {
+    let earlier = first
+        .into_iter()
+        .map(|sample| ((sample.scope.clone(), sample.unit.clone()), sample))
+        .collect::<HashMap<_, _>>();
+    let elapsed_nsec = elapsed.as_nanos() as f64;
+
+    second
+        .into_iter()
+        .map(|sample| {
+            let cpu_percent = earlier
+                .get(&(sample.scope.clone(), sample.unit.clone()))
+                .and_then(|previous| previous.cpu_usage_nsec)
+                .zip(sample.cpu_usage_nsec)
+                .and_then(|(before, after)| after.checked_sub(before))
+                .filter(|_| elapsed_nsec > 0.0)
+                .map(|delta| (delta as f64 / elapsed_nsec * 10_000.0).round() / 100.0);
+            TopUnitRow {
+                unit: sample.unit,
+                scope: sample.scope,
+                memory_current_bytes: sample.memory_current_bytes,
+                cpu_percent,
+                tasks_current: sample.tasks_current,
+            }
+        })
+        .collect()
+}
+
+/// Sorts rows by `metric` descending with `null` values last, then by unit and scope.
+fn rank_rows(rows: &mut [TopUnitRow], metric: RankMetric) {
+    rows.sort_by(|left, right| {
+        match (left.metric_value(metric), right.metric_value(metric)) {
+            (Some(left), Some(right)) => right.total_cmp(&left),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        }
+        .then_with(|| left.unit.cmp(&right.unit))
+        .then_with(|| left.scope.cmp(&right.scope))
+    });
+}
+
+/// Validates and handles `top_units`, sampling counters twice `interval_ms` apart.
+pub async fn handle(
+    state: &AppState,
+    id: Option<Value>,
+    arguments: Option<serde_json::Map<String, Value>>,
+) -> Value {
+    let params: Params = match serde_json::from_value(json!(arguments.unwrap_or_default())) {
+        Ok(value) => value,
+        Err(_) => return json_rpc_invalid_params(id),
+    };
+    let scope = match normalize_scope(params.scope) {
+        Ok(value) => value,
+        Err(err) => return app_error_to_json_rpc(id, err),
+    };
+    let metric = match params
+        .metric
+        .as_deref()
+        .map(str::trim)
+        .map(str::to_ascii_lowercase)
+        .as_deref()
+    {
+        None | Some("memory") => RankMetric::Memory,
+        Some("cpu") => RankMetric::Cpu,
+        Some("tasks") => RankMetric::Tasks,
+        Some(_) => {
+            return app_error_to_json_rpc(
+                id,
+                AppError::bad_request("invalid_metric", "metric must be memory, cpu, or tasks"),
+            );
+        }
+    };
+    let limit = params.limit.unwrap_or(10);
+    if !(1..=100).contains(&limit) {
+        return app_error_to_json_rpc(
+            id,
+            AppError::bad_request("invalid_limit", "limit must be between 1 and 100"),
+        );
+    }
+    let interval_ms = params.interval_ms.unwrap_or(1_000);
+    if !(100..=5_000).contains(&interval_ms) {
+        return app_error_to_json_rpc(
+            id,
+            AppError::bad_request(
+                "invalid_interval",
+                "interval_ms must be between 100 and 5000",
+            ),
+        );
+    }
+
+    let first = match state.unit_provider.sample_service_resources(scope).await {
+        Ok(samples) => samples,
+        Err(err) => return app_error_to_json_rpc(id, err),
+    };
+    let started = Instant::now();
+    tokio::time::sleep(Duration::from_millis(interval_ms)).await;
+    let second = match state.unit_provider.sample_service_resources(scope).await {
+        Ok(samples) => samples,
+        Err(err) => return app_error_to_json_rpc(id, err),
+    };
+    let elapsed = started.elapsed();
+
+    let mut rows = build_rows(first, second, elapsed);
+    let sampled = rows.len();
+    rank_rows(&mut rows, metric);
+    rows.truncate(limit as usize);
+
+    tool_success_response(
+        id,
+        format!(
+            "Returned top {} of {} sampled units by {}",
+            rows.len(),
+            sampled,
+            metric.as_str()
+        ),
+        serde_json::Map::from_iter([
+            ("metric".to_string(), json!(metric.as_str())),
+            ("scope".to_string(), json!(scope.as_str())),
+            ("interval_ms".to_string(), json!(interval_ms)),
+            ("units".to_string(), json!(rows)),
+            ("sampled".to_string(), json!(sampled)),
+            ("returned".to_string(), json!(rows.len())),
+            (
+                "generated_at_utc".to_string(),
+                json!(generated_at_utc_string()),
+            ),
+        ]),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RankMetric, build_rows, rank_rows};
+    use crate::systemd_client::ResourceSample;
+    use std::time::Duration;
+
+    fn sample(unit: &str, cpu_usage_nsec: Option<u64>, memory: Option<u64>) -> ResourceSample {
+        ResourceSample {
+            unit: unit.to_string(),
+            scope: "system".to_string(),
+            memory_current_bytes: memory,
+            cpu_usage_nsec,
+            tasks_current: None,
+        }
+    }
+
+    #[test]
+    fn computes_cpu_rate_and_ranks_nulls_last() {
+        let mut rows = build_rows(
+            vec![
+                sample("busy.service", Some(1_000_000_000), Some(10)),
+                sample("idle.service", Some(5_000), Some(30)),
+            ],
+            vec![
+                sample("busy.service", Some(1_500_000_000), Some(10)),
+                sample("idle.service", Some(5_000), Some(30)),
+                sample("new.service", Some(42), None),
+            ],
+            Duration::from_secs(1),
+        );
+
+        rank_rows(&mut rows, RankMetric::Cpu);
+        assert_eq!(rows[0].unit, "busy.service");
+        assert_eq!(rows[0].cpu_percent, Some(50.0));
+        assert_eq!(rows[1].cpu_percent, Some(0.0));
+        assert_eq!(rows[2].unit, "new.service");
+        assert_eq!(rows[2].cpu_percent, None);
+
+        rank_rows(&mut rows, RankMetric::Memory);
+        assert_eq!(rows[0].unit, "idle.service");
+        assert_eq!(rows[2].unit, "new.service");
+    }
+}
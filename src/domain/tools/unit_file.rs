@@ -0,0 +1,69 @@
+//! Read-only unit file and drop-in inspection MCP handler.
+
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::{
+    AppState,
+    domain::{
+        responses::{generated_at_utc_string, tool_success_response},
+        utils::{SINGLE_SCOPE_MESSAGE, normalize_scope, normalize_typed_unit},
+    },
+    errors::AppError,
+    mcp::rpc::{app_error_to_json_rpc, json_rpc_invalid_params},
+};
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    unit: String,
+    scope: Option<String>,
+}
+
+/// Validates and handles `get_unit_file` for a concrete service manager scope.
+pub async fn handle(
+    state: &AppState,
+    id: Option<Value>,
+    arguments: Option<serde_json::Map<String, Value>>,
+) -> Value {
+    let params: Params = match serde_json::from_value(json!(arguments.unwrap_or_default())) {
+        Ok(value) => value,
+        Err(_) => return json_rpc_invalid_params(id),
+    };
+    let unit = match normalize_typed_unit(params.unit) {
+        Ok(value) => value,
+        Err(err) => return app_error_to_json_rpc(id, err),
+    };
+    let scope = match normalize_scope(params.scope) {
+        Ok(scope) if !scope.is_aggregate() => scope,
+        _ => {
+            return app_error_to_json_rpc(
+                id,
+                AppError::bad_request("invalid_scope", SINGLE_SCOPE_MESSAGE),
+            );
+        }
+    };
+    match state.unit_provider.get_unit_file(&unit, scope).await {
+        Ok(view) => {
+            let message = format!(
+                "Returned {} unit file sections with {} drop-ins",
+                view.sections.len(),
+                view.drop_in_paths.len()
+            );
+            let mut payload = match serde_json::to_value(view) {
+                Ok(Value::Object(object)) => object,
+                _ => {
+                    return app_error_to_json_rpc(
+                        id,
+                        AppError::internal("failed to serialize unit file view".to_string()),
+                    );
+                }
+            };
+            payload.insert(
+                "generated_at_utc".to_string(),
+                json!(generated_at_utc_string()),
+            );
+            tool_success_response(id, message, payload)
+        }
+        Err(err) => app_error_to_json_rpc(id, err),
+    }
+}
@@ -0,0 +1,81 @@
+//! Per-unit cgroup resource usage MCP handler.
+
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::{
+    AppState,
+    domain::{
+        responses::{generated_at_utc_string, tool_success_response},
+        utils::{SINGLE_SCOPE_MESSAGE, normalize_scope, normalize_typed_unit},
+    },
+    errors::AppError,
+    mcp::rpc::{app_error_to_json_rpc, json_rpc_invalid_params},
+    systemd_client::cgroup_interface_for,
+};
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    unit: String,
+    scope: Option<String>,
+}
+
+/// Validates and handles `get_unit_resources` for a concrete service manager scope.
+pub async fn handle(
+    state: &AppState,
+    id: Option<Value>,
+    arguments: Option<serde_json::Map<String, Value>>,
+) -> Value {
+    let params: Params = match serde_json::from_value(json!(arguments.unwrap_or_default())) {
+        Ok(value) => value,
+        Err(_) => return json_rpc_invalid_params(id),
+    };
+    let unit = match normalize_typed_unit(params.unit) {
+        Ok(value) if cgroup_interface_for(&value).is_some() => value,
+        Ok(_) => {
+            return app_error_to_json_rpc(
+                id,
+                AppError::bad_request(
+                    "invalid_unit",
+                    "unit must be a service, slice, scope, socket, mount, or swap unit",
+                ),
+            );
+        }
+        Err(err) => return app_error_to_json_rpc(id, err),
+    };
+    let scope = match normalize_scope(params.scope) {
+        Ok(scope) if !scope.is_aggregate() => scope,
+        _ => {
+            return app_error_to_json_rpc(
+                id,
+                AppError::bad_request("invalid_scope", SINGLE_SCOPE_MESSAGE),
+            );
+        }
+    };
+    match state
+        .unit_provider
+        .unit_resources(scope, std::slice::from_ref(&unit))
+        .await
+    {
+        Ok(mut resources) => match resources.remove(&unit) {
+            Some(resources) => tool_success_response(
+                id,
+                "Returned unit resources".to_string(),
+                serde_json::Map::from_iter([
+                    ("unit".to_string(), json!(unit)),
+                    ("scope".to_string(), json!(scope.as_str())),
+                    ("resources".to_string(), json!(resources)),
+                    (
+                        "generated_at_utc".to_string(),
+                        json!(generated_at_utc_string()),
+                    ),
+                ]),
+            ),
+            None => app_error_to_json_rpc(
+                id,
+                AppError::bad_request("unit_not_found", "systemd unit was not found"),
+            ),
+        },
+        Err(err) => app_error_to_json_rpc(id, err),
+    }
+}
@@ -1,4 +1,4 @@
-//! Detailed systemd service inspection MCP handler.
+//! Detailed systemd unit inspection MCP handler.
 
 use serde::Deserialize;
 use serde_json::{Value, json};
@@ -7,11 +7,10 @@ use crate::{
     AppState,
     domain::{
         responses::tool_success_response,
-        utils::{normalize_scope, normalize_unit},
+        utils::{SINGLE_SCOPE_MESSAGE, normalize_scope, normalize_typed_unit},
     },
     errors::AppError,
     mcp::rpc::{app_error_to_json_rpc, json_rpc_invalid_params},
-    systemd_client::UnitScope,
 };
 
 #[derive(Debug, Deserialize)]
@@ -21,7 +20,7 @@ struct Params {
     transition_limit: Option<u32>,
 }
 
-/// Validates and handles `get_unit_status` for a concrete service manager scope.
+/// Validates and handles `get_unit_status` for any unit type in a concrete manager scope.
 pub async fn handle(
     state: &AppState,
     id: Option<Value>,
@@ -31,22 +30,16 @@ pub async fn handle(
         Ok(value) => value,
         Err(_) => return json_rpc_invalid_params(id),
     };
-    let unit = match normalize_unit(Some(params.unit)) {
-        Ok(Some(value)) if value.ends_with(".service") => value,
-        _ => {
-            return app_error_to_json_rpc(
-                id,
-                AppError::bad_request("invalid_unit", "unit must be a valid .service name"),
-            );
-        }
+    let unit = match normalize_typed_unit(params.unit) {
+        Ok(value) => value,
+        Err(err) => return app_error_to_json_rpc(id, err),
     };
     let scope = match normalize_scope(params.scope) {
-        Ok(UnitScope::System) => UnitScope::System,
-        Ok(UnitScope::User) => UnitScope::User,
+        Ok(scope) if !scope.is_aggregate() => scope,
         _ => {
             return app_error_to_json_rpc(
                 id,
-                AppError::bad_request("invalid_scope", "scope must be system or user"),
+                AppError::bad_request("invalid_scope", SINGLE_SCOPE_MESSAGE),
             );
         }
     };
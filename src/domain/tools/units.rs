@@ -0,0 +1,210 @@
+//! Generic systemd unit listing MCP handler covering every unit type.
+
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+
+use crate::AppState;
+use crate::domain::responses::{generated_at_utc_string, paginate_rows, tool_success_response};
+use crate::domain::utils::{
+    normalize_name_contains, normalize_scope, normalize_service_state, normalize_unit_type,
+    normalize_units_limit,
+};
+use crate::errors::AppError;
+use crate::mcp::rpc::{app_error_to_json_rpc, json_rpc_invalid_params};
+use crate::systemd_client::{UnitInfo, UnitScope};
+
+use super::UnitsQueryParams;
+
+#[derive(Debug)]
+struct NormalizedUnitsQuery {
+    scope: UnitScope,
+    unit_type: Option<String>,
+    state_filter: Option<String>,
+    name_contains_filter: Option<String>,
+    limit: usize,
+    summary_enabled: bool,
+}
+
+enum NormalizeUnitsError {
+    InvalidParams,
+    Domain(AppError),
+}
+
+#[derive(Debug, Serialize)]
+struct FailedUnitSummary {
+    unit: String,
+    unit_type: String,
+    sub_state: String,
+    since_utc: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct UnitSummary {
+    counts_by_type: BTreeMap<String, usize>,
+    counts_by_active_state: BTreeMap<String, usize>,
+    failed_units: Vec<FailedUnitSummary>,
+    degraded_hint: Option<String>,
+}
+
+/// Builds `list_units` summary payload for triage mode.
+///
+/// Includes per-type and per-state counts, a capped failed-unit list, and an
+/// optional degraded hint naming how many units of any type have failed.
+fn build_unit_summary(units: &[UnitInfo]) -> UnitSummary {
+    let mut counts_by_type = BTreeMap::new();
+    let mut counts_by_active_state = BTreeMap::new();
+    for unit in units {
+        *counts_by_type.entry(unit.unit_type.clone()).or_insert(0) += 1;
+        *counts_by_active_state
+            .entry(unit.active_state.clone())
+            .or_insert(0) += 1;
+    }
+
+    let mut failed_units = units
+        .iter()
+        .filter(|unit| unit.active_state.eq_ignore_ascii_case("failed"))
+        .map(|unit| FailedUnitSummary {
+            unit: unit.unit.clone(),
+            unit_type: unit.unit_type.clone(),
+            sub_state: unit.sub_state.clone(),
+            since_utc: unit.since_utc.clone(),
+        })
+        .collect::<Vec<_>>();
+
+    failed_units.sort_by(|left, right| left.unit.cmp(&right.unit));
+    failed_units.truncate(10);
+
+    let degraded_hint = if failed_units.is_empty() {
+        None
+    } else {
+        Some(format!(
+            "Detected {} failed unit(s); review failed_units for triage",
+            failed_units.len()
+        ))
+    };
+
+    UnitSummary {
+        counts_by_type,
+        counts_by_active_state,
+        failed_units,
+        degraded_hint,
+    }
+}
+
+/// Parses and normalizes `list_units` arguments into a typed execution query.
+fn normalize_units_query(
+    arguments: Option<serde_json::Map<String, Value>>,
+) -> Result<NormalizedUnitsQuery, NormalizeUnitsError> {
+    let query_params: UnitsQueryParams =
+        serde_json::from_value(json!(arguments.unwrap_or_default()))
+            .map_err(|_| NormalizeUnitsError::InvalidParams)?;
+
+    Ok(NormalizedUnitsQuery {
+        scope: normalize_scope(query_params.scope).map_err(NormalizeUnitsError::Domain)?,
+        unit_type: normalize_unit_type(query_params.unit_type)
+            .map_err(NormalizeUnitsError::Domain)?,
+        state_filter: normalize_service_state(query_params.state)
+            .map_err(NormalizeUnitsError::Domain)?,
+        name_contains_filter: normalize_name_contains(query_params.name_contains),
+        limit: normalize_units_limit(query_params.limit).map_err(NormalizeUnitsError::Domain)?,
+        summary_enabled: query_params.summary.unwrap_or(false),
+    })
+}
+
+/// Sorts unit rows by name, optionally placing failed units first.
+fn sort_units(units: &mut [UnitInfo], failed_first: bool) {
+    units.sort_by(|left, right| {
+        let ordering = if failed_first {
+            let left_failed = left.active_state.eq_ignore_ascii_case("failed");
+            let right_failed = right.active_state.eq_ignore_ascii_case("failed");
+            right_failed.cmp(&left_failed)
+        } else {
+            std::cmp::Ordering::Equal
+        };
+        ordering
+            .then_with(|| left.unit.cmp(&right.unit))
+            .then_with(|| left.scope.cmp(&right.scope))
+    });
+}
+
+/// Handles `list_units` tool execution.
+///
+/// Applies the same scope, state, name, limit, and summary semantics as
+/// `list_services` across every unit type, with an optional `type` filter.
+pub async fn handle_list_units(
+    state: &AppState,
+    id: Option<Value>,
+    arguments: Option<serde_json::Map<String, Value>>,
+) -> Value {
+    let normalized = match normalize_units_query(arguments) {
+        Ok(value) => value,
+        Err(NormalizeUnitsError::InvalidParams) => return json_rpc_invalid_params(id),
+        Err(NormalizeUnitsError::Domain(err)) => return app_error_to_json_rpc(id, err),
+    };
+
+    match state
+        .unit_provider
+        .list_units(normalized.scope, normalized.unit_type.as_deref())
+        .await
+    {
+        Ok(units) => {
+            let mut units = units
+                .into_iter()
+                .filter(|unit| {
+                    normalized
+                        .unit_type
+                        .as_deref()
+                        .is_none_or(|expected| unit.unit_type == expected)
+                })
+                .filter(|unit| {
+                    normalized
+                        .state_filter
+                        .as_deref()
+                        .is_none_or(|expected| unit.active_state.eq_ignore_ascii_case(expected))
+                })
+                .filter(|unit| {
+                    normalized
+                        .name_contains_filter
+                        .as_deref()
+                        .is_none_or(|needle| unit.unit.contains(needle))
+                })
+                .collect::<Vec<_>>();
+
+            let failed_first = normalized.state_filter.as_deref() == Some("failed");
+            sort_units(&mut units, failed_first);
+
+            let page = paginate_rows(units, normalized.limit);
+            let generated_at_utc = generated_at_utc_string();
+
+            if normalized.summary_enabled {
+                let summary = build_unit_summary(&page.rows);
+
+                return tool_success_response(
+                    id,
+                    "Returned unit triage summary".to_string(),
+                    serde_json::Map::from_iter([
+                        ("summary".to_string(), json!(summary)),
+                        ("total".to_string(), json!(page.total)),
+                        ("returned".to_string(), json!(page.returned)),
+                        ("truncated".to_string(), json!(page.truncated)),
+                        ("generated_at_utc".to_string(), json!(generated_at_utc)),
+                    ]),
+                );
+            }
+
+            tool_success_response(
+                id,
+                format!("Returned {} of {} units", page.returned, page.total),
+                serde_json::Map::from_iter([
+                    ("units".to_string(), json!(page.rows)),
+                    ("total".to_string(), json!(page.total)),
+                    ("returned".to_string(), json!(page.returned)),
+                    ("truncated".to_string(), json!(page.truncated)),
+                    ("generated_at_utc".to_string(), json!(generated_at_utc)),
+                ]),
+            )
+        }
+        Err(err) => app_error_to_json_rpc(id, err),
+    }
+}
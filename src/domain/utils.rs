@@ -2,7 +2,9 @@
 
 use crate::{
     errors::AppError,
-    systemd_client::{UnitScope, UnitStatus},
+    systemd_client::{
+        BootSelector, DependencyDirection, MachineName, UnitScope, UnitStatus, unit_type_of,
+    },
 };
 use chrono::{DateTime, Utc};
 
@@ -12,6 +14,21 @@ pub const MAX_SERVICES_LIMIT: usize = 1_000;
 pub const DEFAULT_SERVICES_LIMIT: usize = 200;
 pub const MAX_TIMERS_LIMIT: usize = 1_000;
 pub const DEFAULT_TIMERS_LIMIT: usize = 200;
+pub const MAX_UNITS_LIMIT: usize = 1_000;
+pub const DEFAULT_UNITS_LIMIT: usize = 200;
+pub const VALID_UNIT_TYPES: [&str; 11] = [
+    "service",
+    "socket",
+    "target",
+    "device",
+    "mount",
+    "automount",
+    "swap",
+    "timer",
+    "path",
+    "slice",
+    "scope",
+];
 pub const VALID_SERVICE_STATES: [&str; 6] = [
     "active",
     "inactive",
@@ -90,6 +107,9 @@ pub fn normalize_priority(priority: Option<String>) -> Result<Option<String>, Ap
 }
 
 /// Returns whether a unit name contains only allowed ASCII identifier characters.
+///
+/// `\` is accepted for systemd's `\xNN` escapes, as in machine scope units
+/// like `machine-web\x2d1.scope`.
 fn is_valid_unit_name_chars(s: &str) -> bool {
     s.chars().all(|character| {
         character.is_ascii_alphanumeric()
@@ -98,6 +118,7 @@ fn is_valid_unit_name_chars(s: &str) -> bool {
             || character == '@'
             || character == ':'
             || character == '.'
+            || character == '\\'
     })
 }
 
@@ -111,31 +132,118 @@ pub fn normalize_unit(unit: Option<String>) -> Result<Option<String>, AppError>
     if normalized.is_empty() || !is_valid_unit_name_chars(normalized) {
         return Err(AppError::bad_request(
             "invalid_unit",
-            "unit must contain only alphanumeric characters, dashes, underscores, dots, @, and :",
+            "unit must contain only alphanumeric characters, dashes, underscores, dots, @, :, and \\",
         ));
     }
 
     Ok(Some(normalized.to_string()))
 }
 
-/// Normalizes unit-query scope across list tools.
-///
-/// Accepted values are `system`, `user`, and `both` (case-insensitive).
-/// Missing values default to `system`.
-pub fn normalize_scope(scope: Option<String>) -> Result<UnitScope, AppError> {
-    match scope
+/// Validates a required unit name whose suffix is a known systemd unit type.
+pub fn normalize_typed_unit(unit: String) -> Result<String, AppError> {
+    match normalize_unit(Some(unit)) {
+        Ok(Some(value))
+            if unit_type_of(&value).is_some_and(|kind| VALID_UNIT_TYPES.contains(&kind)) =>
+        {
+            Ok(value)
+        }
+        _ => Err(AppError::bad_request(
+            "invalid_unit",
+            "unit must be a valid systemd unit name with a known type suffix",
+        )),
+    }
+}
+
+/// Normalizes dependency walk direction; missing values default to `forward`.
+pub fn normalize_dependency_direction(
+    direction: Option<String>,
+) -> Result<DependencyDirection, AppError> {
+    match direction
         .as_deref()
         .map(str::trim)
         .filter(|value| !value.is_empty())
         .map(str::to_ascii_lowercase)
         .as_deref()
     {
+        None | Some("forward") => Ok(DependencyDirection::Forward),
+        Some("reverse") => Ok(DependencyDirection::Reverse),
+        Some(_) => Err(AppError::bad_request(
+            "invalid_direction",
+            "direction must be forward or reverse",
+        )),
+    }
+}
+
+/// Normalizes a journal boot selector.
+///
+/// Accepts `current` (offset `0`), a non-positive offset as a JSON integer or
+/// string (`-1` is the previous boot), or a 32-character hexadecimal boot ID.
+pub fn normalize_boot_selector(boot: &serde_json::Value) -> Result<BootSelector, AppError> {
+    let invalid = || {
+        AppError::bad_request(
+            "invalid_boot",
+            "boot must be current, a non-positive offset, or a 32-character boot ID",
+        )
+    };
+    let value = match boot {
+        serde_json::Value::Number(number) => number.to_string(),
+        serde_json::Value::String(value) => value.trim().to_ascii_lowercase(),
+        _ => return Err(invalid()),
+    };
+    if value == "current" {
+        return Ok(BootSelector::Offset(0));
+    }
+    if let Ok(offset) = value.parse::<i64>() {
+        return if offset <= 0 {
+            Ok(BootSelector::Offset(offset))
+        } else {
+            Err(invalid())
+        };
+    }
+    if value.len() == 32 && value.chars().all(|ch| ch.is_ascii_hexdigit()) {
+        return Ok(BootSelector::Id(value));
+    }
+    Err(invalid())
+}
+
+/// Rejection message for tools that inspect one concrete scope.
+///
+/// Lists every form `normalize_scope` accepts except the aggregate `both` and
+/// `users:all`.
+pub const SINGLE_SCOPE_MESSAGE: &str =
+    "scope must be one of: system, user, user:<uid>, machine:<name>";
+
+/// Normalizes unit-query scope across list tools.
+///
+/// Accepted values are `system`, `user`, `both`, `user:<uid>`, `users:all`
+/// (case-insensitive), and `machine:<name>`, whose prefix is case-insensitive
+/// while the machine name is kept as given. UIDs must be plain decimal numbers.
+/// Missing values default to `system`. The UID and machine allowlists are
+/// enforced by the provider.
+pub fn normalize_scope(scope: Option<String>) -> Result<UnitScope, AppError> {
+    let scope = scope
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+    if let Some((prefix, name)) = scope.and_then(|value| value.split_at_checked("machine:".len()))
+        && prefix.eq_ignore_ascii_case("machine:")
+    {
+        return MachineName::parse(name)
+            .map(UnitScope::Machine)
+            .ok_or_else(|| {
+                AppError::bad_request("invalid_scope", "machine scope must be machine:<name>")
+            });
+    }
+    match scope.map(str::to_ascii_lowercase).as_deref() {
         None | Some("system") => Ok(UnitScope::System),
         Some("user") => Ok(UnitScope::User),
         Some("both") => Ok(UnitScope::Both),
+        Some("users:all") => Ok(UnitScope::AllUsers),
+        Some(value) if value.starts_with("user:") => UnitScope::from_row_scope(value)
+            .ok_or_else(|| AppError::bad_request("invalid_scope", "user scope must be user:<uid>")),
         _ => Err(AppError::bad_request(
             "invalid_scope",
-            "scope must be one of: system, user, both",
+            "scope must be one of: system, user, both, user:<uid>, users:all, machine:<name>",
         )),
     }
 }
@@ -164,14 +272,14 @@ pub fn normalize_service_state(state: Option<String>) -> Result<Option<String>,
     if normalized.is_empty() {
         return Err(AppError::bad_request(
             "invalid_state",
-            "state must be one of: active, inactive, failed, activating, deactivating, reloading",
+            "state must be one of: active, inactive, failed, activating, deactivating, reloading, flapping",
         ));
     }
 
-    if !VALID_SERVICE_STATES.contains(&normalized.as_str()) {
+    if normalized != "flapping" && !VALID_SERVICE_STATES.contains(&normalized.as_str()) {
         return Err(AppError::bad_request(
             "invalid_state",
-            "state must be one of: active, inactive, failed, activating, deactivating, reloading",
+            "state must be one of: active, inactive, failed, activating, deactivating, reloading, flapping",
         ));
     }
 
@@ -191,6 +299,38 @@ pub fn normalize_services_limit(limit: Option<u32>) -> Result<usize, AppError> {
     Ok(limit as usize)
 }
 
+/// Normalizes unit-list limits with default and hard cap enforcement.
+pub fn normalize_units_limit(limit: Option<u32>) -> Result<usize, AppError> {
+    let limit = limit.unwrap_or(DEFAULT_UNITS_LIMIT as u32);
+    if limit == 0 || limit > MAX_UNITS_LIMIT as u32 {
+        return Err(AppError::bad_request(
+            "invalid_limit",
+            "limit must be between 1 and 1000",
+        ));
+    }
+
+    Ok(limit as usize)
+}
+
+/// Normalizes and validates a unit type filter against systemd unit suffixes.
+///
+/// Matching is case-insensitive and a leading dot (`.mount`) is accepted.
+pub fn normalize_unit_type(unit_type: Option<String>) -> Result<Option<String>, AppError> {
+    let Some(value) = unit_type else {
+        return Ok(None);
+    };
+
+    let normalized = value.trim().trim_start_matches('.').to_ascii_lowercase();
+    if !VALID_UNIT_TYPES.contains(&normalized.as_str()) {
+        return Err(AppError::bad_request(
+            "invalid_unit_type",
+            "type must be one of: service, socket, target, device, mount, automount, swap, timer, path, slice, scope",
+        ));
+    }
+
+    Ok(Some(normalized))
+}
+
 /// Normalizes timer `limit` with default and hard cap enforcement.
 ///
 /// Behavior:
@@ -285,6 +425,8 @@ pub fn normalize_timers_order(order: Option<String>) -> Result<String, AppError>
 }
 
 /// Filters service rows by active state using case-insensitive matching.
+///
+/// The pseudo-state `flapping` selects rows classified as flapping instead.
 pub fn filter_services_by_state(services: Vec<UnitStatus>, state: Option<&str>) -> Vec<UnitStatus> {
     let Some(state) = state else {
         return services;
@@ -292,7 +434,13 @@ pub fn filter_services_by_state(services: Vec<UnitStatus>, state: Option<&str>)
 
     services
         .into_iter()
-        .filter(|service| service.active_state.eq_ignore_ascii_case(state))
+        .filter(|service| {
+            if state == "flapping" {
+                service.flapping
+            } else {
+                service.active_state.eq_ignore_ascii_case(state)
+            }
+        })
         .collect()
 }
 
@@ -333,11 +481,13 @@ pub fn sort_services(services: &mut [UnitStatus], failed_first: bool) {
 #[cfg(test)]
 mod tests {
     use super::{
-        filter_services_by_name_contains, filter_services_by_state, normalize_name_contains,
-        normalize_scope, normalize_service_state, normalize_services_limit, normalize_timer_state,
-        normalize_timers_limit, normalize_timers_order, normalize_timers_sort, sort_services,
+        filter_services_by_name_contains, filter_services_by_state, normalize_boot_selector,
+        normalize_name_contains, normalize_scope, normalize_service_state,
+        normalize_services_limit, normalize_timer_state, normalize_timers_limit,
+        normalize_timers_order, normalize_timers_sort, normalize_unit, normalize_unit_type,
+        sort_services,
     };
-    use crate::systemd_client::{UnitScope, UnitStatus};
+    use crate::systemd_client::{BootSelector, UnitScope, UnitStatus};
 
     #[test]
     fn normalizes_service_state_test() {
@@ -345,6 +495,35 @@ mod tests {
         assert_eq!(state.as_deref(), Some("failed"));
     }
 
+    #[test]
+    fn normalizes_boot_selectors_and_rejects_future_offsets() {
+        assert_eq!(
+            normalize_boot_selector(&serde_json::json!("Current")).expect("current"),
+            BootSelector::Offset(0)
+        );
+        assert_eq!(
+            normalize_boot_selector(&serde_json::json!(-1)).expect("integer offset"),
+            BootSelector::Offset(-1)
+        );
+        assert_eq!(
+            normalize_boot_selector(&serde_json::json!("-2")).expect("string offset"),
+            BootSelector::Offset(-2)
+        );
+        assert_eq!(
+            normalize_boot_selector(&serde_json::json!("ABCDEF0123456789abcdef0123456789"))
+                .expect("boot id"),
+            BootSelector::Id("abcdef0123456789abcdef0123456789".to_string())
+        );
+        for invalid in [
+            serde_json::json!(1),
+            serde_json::json!("previous"),
+            serde_json::json!("abc"),
+            serde_json::json!(true),
+        ] {
+            assert!(normalize_boot_selector(&invalid).is_err());
+        }
+    }
+
     #[test]
     fn normalizes_scope_and_defaults_to_system() {
         assert_eq!(
@@ -361,12 +540,69 @@ mod tests {
         );
     }
 
+    #[test]
+    fn accepts_escaped_unit_names_and_rejects_path_characters() {
+        assert_eq!(
+            normalize_unit(Some(" machine-web\\x2d1.scope ".to_string())).expect("escaped unit"),
+            Some("machine-web\\x2d1.scope".to_string())
+        );
+        for invalid in ["../etc/shadow", "a b.service", "a/b.service", ""] {
+            assert!(normalize_unit(Some(invalid.to_string())).is_err());
+        }
+    }
+
     #[test]
     fn rejects_invalid_scope_value() {
         let error = normalize_scope(Some("global".to_string())).expect_err("invalid scope");
         assert!(error.to_string().contains("bad request"));
     }
 
+    #[test]
+    fn normalizes_machine_scopes_keeping_name_case() {
+        let scope = normalize_scope(Some(" MACHINE:Web-1 ".to_string())).expect("machine scope");
+        assert_eq!(scope.as_str(), "machine:Web-1");
+        for invalid in [
+            "machine:",
+            "machine:../web",
+            "machine:web 1",
+            "machine:-web",
+        ] {
+            let error = normalize_scope(Some(invalid.to_string())).expect_err(invalid);
+            assert!(matches!(
+                error,
+                crate::errors::AppError::BadRequest {
+                    code: "invalid_scope",
+                    ..
+                }
+            ));
+        }
+    }
+
+    #[test]
+    fn normalizes_per_user_scopes() {
+        assert_eq!(
+            normalize_scope(Some(" USER:1000 ".to_string())).expect("user id scope"),
+            UnitScope::UserId(1000)
+        );
+        assert_eq!(
+            normalize_scope(Some("users:all".to_string())).expect("all users scope"),
+            UnitScope::AllUsers
+        );
+        for invalid in [
+            "user:",
+            "user:alice",
+            "user:-1",
+            "user:+1",
+            "user:4294967296",
+            "users",
+        ] {
+            assert!(
+                normalize_scope(Some(invalid.to_string())).is_err(),
+                "{invalid}"
+            );
+        }
+    }
+
     #[test]
     fn rejects_invalid_service_state() {
         let state = normalize_service_state(Some("running".to_string()));
@@ -389,6 +625,16 @@ mod tests {
                 main_pid: None,
                 exec_main_status: None,
                 result: None,
+                restart_count: None,
+                start_limit: None,
+                flapping: false,
+                pending_job: None,
+                exit_code: None,
+                exit_signal: None,
+                exec_main_start_utc: None,
+                exec_main_exit_utc: None,
+                oom_killed: false,
+                oom_kill_source: None,
             },
             UnitStatus {
                 scope: "system".to_string(),
@@ -402,6 +648,16 @@ mod tests {
                 main_pid: None,
                 exec_main_status: None,
                 result: None,
+                restart_count: None,
+                start_limit: None,
+                flapping: false,
+                pending_job: None,
+                exit_code: None,
+                exit_signal: None,
+                exec_main_start_utc: None,
+                exec_main_exit_utc: None,
+                oom_killed: false,
+                oom_kill_source: None,
             },
         ];
 
@@ -428,6 +684,18 @@ mod tests {
         assert!(error.to_string().contains("bad request"));
     }
 
+    #[test]
+    fn normalizes_unit_type_with_optional_dot() {
+        let unit_type = normalize_unit_type(Some(" .MoUnT ".to_string())).expect("valid type");
+        assert_eq!(unit_type.as_deref(), Some("mount"));
+    }
+
+    #[test]
+    fn rejects_unknown_unit_type() {
+        let error = normalize_unit_type(Some("container".to_string())).expect_err("invalid type");
+        assert!(error.to_string().contains("bad request"));
+    }
+
     #[test]
     fn rejects_invalid_timers_limit() {
         let error = normalize_timers_limit(Some(1_001)).expect_err("invalid limit");
@@ -473,6 +741,16 @@ mod tests {
                 main_pid: None,
                 exec_main_status: None,
                 result: None,
+                restart_count: None,
+                start_limit: None,
+                flapping: false,
+                pending_job: None,
+                exit_code: None,
+                exit_signal: None,
+                exec_main_start_utc: None,
+                exec_main_exit_utc: None,
+                oom_killed: false,
+                oom_kill_source: None,
             },
             UnitStatus {
                 scope: "system".to_string(),
@@ -486,6 +764,16 @@ mod tests {
                 main_pid: None,
                 exec_main_status: None,
                 result: None,
+                restart_count: None,
+                start_limit: None,
+                flapping: false,
+                pending_job: None,
+                exit_code: None,
+                exit_signal: None,
+                exec_main_start_utc: None,
+                exec_main_exit_utc: None,
+                oom_killed: false,
+                oom_kill_source: None,
             },
         ];
 
@@ -509,6 +797,16 @@ mod tests {
                 main_pid: None,
                 exec_main_status: None,
                 result: None,
+                restart_count: None,
+                start_limit: None,
+                flapping: false,
+                pending_job: None,
+                exit_code: None,
+                exit_signal: None,
+                exec_main_start_utc: None,
+                exec_main_exit_utc: None,
+                oom_killed: false,
+                oom_kill_source: None,
             },
             UnitStatus {
                 scope: "system".to_string(),
@@ -522,6 +820,16 @@ mod tests {
                 main_pid: None,
                 exec_main_status: None,
                 result: None,
+                restart_count: None,
+                start_limit: None,
+                flapping: false,
+                pending_job: None,
+                exit_code: None,
+                exit_signal: None,
+                exec_main_start_utc: None,
+                exec_main_exit_utc: None,
+                oom_killed: false,
+                oom_kill_source: None,
             },
         ];
 
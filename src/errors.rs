@@ -85,6 +85,17 @@ impl AppError {
         }
     }
 
+    /// Creates an internal error for a D-Bus connection that is unavailable.
+    ///
+    /// Used while a scope's reconnect backoff is pending, so callers can tell
+    /// bus outages apart from other internal failures by `dbus_error`.
+    pub fn dbus(message: impl Into<String>) -> Self {
+        Self::Internal {
+            code: "dbus_error",
+            message: message.into(),
+        }
+    }
+
     /// Creates a not-implemented error for explicitly unsupported behavior.
     pub fn not_implemented(code: &'static str, message: &'static str) -> Self {
         Self::NotImplemented { code, message }
@@ -32,7 +32,7 @@ pub struct DiscoveryResponse {
 
 #[derive(Debug, Serialize)]
 pub struct SystemdStatusResponse {
-    pub scope: &'static str,
+    pub scope: String,
     pub status: String,
 }
 
@@ -41,6 +41,42 @@ pub async fn health() -> Json<HealthResponse> {
     Json(HealthResponse { status: "ok" })
 }
 
+/// Readiness handler reporting the provider's shared connection health.
+///
+/// Returns `200 OK` with `status: ready` while the system-scope connection is
+/// open (or the provider keeps no connections); otherwise `503` with the
+/// structured HTTP error shape and per-scope connection health in `details`.
+/// The user scope is reported but does not gate readiness because many hosts
+/// run without a session bus.
+pub async fn ready(State(state): State<AppState>) -> Response {
+    let connections = state.unit_provider.connection_health().await;
+    let system_ready = connections
+        .iter()
+        .filter(|health| health.scope == UnitScope::System.as_str())
+        .all(|health| health.connected);
+
+    if system_ready {
+        return (
+            StatusCode::OK,
+            Json(json!({
+                "status": "ready",
+                "connections": connections,
+            })),
+        )
+            .into_response();
+    }
+
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(ErrorResponse {
+            code: "not_ready".to_string(),
+            message: "system dbus connection is unavailable".to_string(),
+            details: json!({ "connections": connections }),
+        }),
+    )
+        .into_response()
+}
+
 /// Public MCP discovery metadata handler.
 ///
 /// Exposes only package identity and MCP endpoint path.
@@ -77,7 +113,7 @@ pub async fn systemd_user_status(State(state): State<AppState>) -> Response {
 /// repository-wide structured error response shape with the manager state in
 /// `details`.
 async fn systemd_status(state: &AppState, scope: UnitScope) -> Response {
-    let scope_name = scope.as_str();
+    let scope_name = scope.as_str().into_owned();
     let status = match state.unit_provider.system_state(scope).await {
         Ok(status) => status,
         Err(err) => return err.into_response(),
@@ -6,6 +6,7 @@ use axum::{
 };
 
 pub mod auth;
+pub mod calendar;
 pub mod config;
 pub mod domain;
 pub mod errors;
@@ -15,6 +16,9 @@ pub mod mcp;
 pub mod podman;
 pub mod rate_limit;
 pub mod systemd_client;
+pub mod time_zone;
+pub mod unit_cache;
+pub mod unit_files;
 
 use podman::{CliPodmanProvider, PodmanProvider};
 use rate_limit::{RateLimitPolicy, RateLimiter};
@@ -77,6 +81,7 @@ pub fn build_app(state: AppState) -> Router {
             "/systemd/user/status",
             get(http::handlers::systemd_user_status),
         )
+        .route("/ready", get(http::handlers::ready))
         .route_layer(middleware::from_fn_with_state(
             state.clone(),
             auth::require_bearer_token,
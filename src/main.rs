@@ -4,7 +4,8 @@ use systemd_monitoring_mcp::{
     AppState, build_app,
     config::Config,
     logging,
-    systemd_client::{DbusSystemdClient, ensure_systemd_available},
+    systemd_client::{DbusSystemdClient, UnitProvider, ensure_systemd_available},
+    unit_cache::CachedUnitProvider,
 };
 use tracing::info;
 
@@ -14,13 +15,23 @@ use tracing::info;
 ///
 /// The configured rate and burst are injected into the one process-wide bucket
 /// and logged without exposing the bearer token.
+/// With `UNIT_CACHE_ENABLED`, service and timer listings are served by the
+/// signal-fed unit cache.
+/// `USER_SCOPE_UIDS` lists the users whose managers per-user scopes may reach,
+/// and `MACHINE_SCOPES` the containers that machine scopes may reach.
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     logging::init_logging();
 
     let config = Config::from_env()?;
     ensure_systemd_available().await?;
 
-    let provider = Arc::new(DbusSystemdClient::new());
+    let mut provider: Arc<dyn UnitProvider> = Arc::new(
+        DbusSystemdClient::with_allowed_uids(config.user_scope_uids.clone())
+            .with_machine_buses(config.machine_buses.clone()),
+    );
+    if config.unit_cache_enabled {
+        provider = CachedUnitProvider::spawn(provider);
+    }
     let bind_socket = config.bind_socket()?;
     let state = AppState::new_with_rate_limit(
         config.api_token.clone(),
@@ -35,6 +46,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         bind_port = config.bind_port,
         rate_limit_requests_per_second = config.rate_limit_requests_per_second,
         rate_limit_burst = config.rate_limit_burst,
+        unit_cache_enabled = config.unit_cache_enabled,
+        user_scope_uids = ?config.user_scope_uids,
+        machine_scopes = ?config.machine_buses.keys().collect::<Vec<_>>(),
         "server starting"
     );
 
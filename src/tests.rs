@@ -1,6 +1,9 @@
-use std::sync::{
-    Arc,
-    atomic::{AtomicUsize, Ordering},
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
 };
 
 use axum::{
@@ -11,9 +14,16 @@ use http_body_util::BodyExt;
 use tower::ServiceExt;
 
 use crate::systemd_client::{
-    JournalLogEntry, LogOrder, LogQuery, LogQueryResult, TimerStatus, UnitProvider, UnitScope,
-    UnitStatus,
+    BootRecord, BootTiming, ConnectionHealth, CoredumpInfo, DependencyDirection, HostIdentity,
+    HostOverview, JobInfo, JobReference, JournalDirectoryUsage, JournalLogEntry, LoadAverage,
+    LogOrder, LogQuery, LogQueryResult, LoginInventory, LoginSeat, LoginSession, LoginUser,
+    MachineInfo, ManagerOverview, MemoryTotals, MountUsage, PendingJob, ServiceSecuritySettings,
+    StartLimitState, StorageReport, TimeSettings, TimerCalendarSpec, TimerMonotonicSpec,
+    TimerStatus, UnitActivation, UnitEvent, UnitInfo, UnitProvider, UnitRefresh, UnitResources,
+    UnitRun, UnitRunHistory, UnitScope, UnitStatus, UnitTypeDetails,
 };
+use crate::unit_cache::CachedUnitProvider;
+use crate::unit_files::{UnitFileSources, UnitFileView, build_unit_file_view};
 
 use super::*;
 
@@ -23,6 +33,66 @@ struct DegradedProvider;
 
 struct ScopeCollisionProvider;
 
+fn hardened_security_settings() -> ServiceSecuritySettings {
+    ServiceSecuritySettings {
+        user: Some(String::new()),
+        dynamic_user: Some(true),
+        no_new_privileges: Some(true),
+        protect_system: Some("strict".to_string()),
+        protect_home: Some("yes".to_string()),
+        protect_proc: Some("invisible".to_string()),
+        private_tmp: Some(true),
+        private_devices: Some(true),
+        private_network: Some(false),
+        private_users: Some(true),
+        protect_kernel_tunables: Some(true),
+        protect_kernel_modules: Some(true),
+        protect_kernel_logs: Some(true),
+        protect_control_groups: Some(true),
+        protect_clock: Some(true),
+        protect_hostname: Some(true),
+        capability_bounding_set: Some(0),
+        ambient_capabilities: Some(0),
+        restrict_address_families: Some((true, vec!["AF_INET".to_string()])),
+        system_call_filter: Some((true, vec!["@system-service".to_string()])),
+        system_call_architectures: Some(vec!["native".to_string()]),
+        memory_deny_write_execute: Some(true),
+        restrict_realtime: Some(true),
+        restrict_suid_sgid: Some(true),
+        lock_personality: Some(true),
+    }
+}
+
+fn unhardened_security_settings() -> ServiceSecuritySettings {
+    ServiceSecuritySettings {
+        user: Some(String::new()),
+        dynamic_user: Some(false),
+        no_new_privileges: Some(false),
+        protect_system: Some("no".to_string()),
+        protect_home: Some("no".to_string()),
+        protect_proc: Some("default".to_string()),
+        private_tmp: Some(false),
+        private_devices: Some(false),
+        private_network: Some(false),
+        private_users: Some(false),
+        protect_kernel_tunables: Some(false),
+        protect_kernel_modules: Some(false),
+        protect_kernel_logs: Some(false),
+        protect_control_groups: Some(false),
+        protect_clock: Some(false),
+        protect_hostname: Some(false),
+        capability_bounding_set: Some(u64::from(u32::MAX)),
+        ambient_capabilities: Some(0),
+        restrict_address_families: Some((false, Vec::new())),
+        system_call_filter: Some((false, Vec::new())),
+        system_call_architectures: Some(Vec::new()),
+        memory_deny_write_execute: Some(false),
+        restrict_realtime: Some(false),
+        restrict_suid_sgid: Some(false),
+        lock_personality: Some(false),
+    }
+}
+
 fn system_services() -> Vec<UnitStatus> {
     vec![
         UnitStatus {
@@ -37,6 +107,20 @@ fn system_services() -> Vec<UnitStatus> {
             main_pid: Some(3001),
             exec_main_status: Some(0),
             result: Some("success".to_string()),
+            restart_count: Some(12),
+            start_limit: Some(StartLimitState {
+                burst: Some(5),
+                interval_usec: Some(10_000_000),
+                hit: false,
+            }),
+            flapping: true,
+            pending_job: None,
+            exit_code: None,
+            exit_signal: None,
+            exec_main_start_utc: None,
+            exec_main_exit_utc: None,
+            oom_killed: false,
+            oom_kill_source: None,
         },
         UnitStatus {
             scope: "system".to_string(),
@@ -50,6 +134,19 @@ fn system_services() -> Vec<UnitStatus> {
             main_pid: None,
             exec_main_status: None,
             result: None,
+            restart_count: None,
+            start_limit: None,
+            flapping: false,
+            pending_job: Some(PendingJob {
+                job_id: 812,
+                job_type: "start".to_string(),
+            }),
+            exit_code: None,
+            exit_signal: None,
+            exec_main_start_utc: None,
+            exec_main_exit_utc: None,
+            oom_killed: false,
+            oom_kill_source: None,
         },
         UnitStatus {
             scope: "system".to_string(),
@@ -63,6 +160,16 @@ fn system_services() -> Vec<UnitStatus> {
             main_pid: Some(4001),
             exec_main_status: Some(1),
             result: Some("exit-code".to_string()),
+            restart_count: None,
+            start_limit: None,
+            flapping: false,
+            pending_job: None,
+            exit_code: Some("exited".to_string()),
+            exit_signal: None,
+            exec_main_start_utc: Some("2026-02-27T23:59:58.000Z".to_string()),
+            exec_main_exit_utc: Some("2026-02-28T00:00:00.000Z".to_string()),
+            oom_killed: false,
+            oom_kill_source: None,
         },
     ]
 }
@@ -80,6 +187,16 @@ fn user_services() -> Vec<UnitStatus> {
         main_pid: Some(5001),
         exec_main_status: Some(0),
         result: Some("success".to_string()),
+        restart_count: None,
+        start_limit: None,
+        flapping: false,
+        pending_job: None,
+        exit_code: None,
+        exit_signal: None,
+        exec_main_start_utc: None,
+        exec_main_exit_utc: None,
+        oom_killed: false,
+        oom_kill_source: None,
     }]
 }
 
@@ -97,6 +214,14 @@ fn system_timers() -> Vec<TimerStatus> {
             trigger_unit: Some("backup.service".to_string()),
             persistent: Some(true),
             result: Some("success".to_string()),
+            calendar: vec![TimerCalendarSpec {
+                base: "OnCalendar".to_string(),
+                expression: "2099-*-01 00:00:00 UTC".to_string(),
+                next_elapse_utc: Some("2099-01-01T00:00:00.000Z".to_string()),
+            }],
+            monotonic: Vec::new(),
+            accuracy_usec: Some(60_000_000),
+            randomized_delay_usec: Some(1_800_000_000),
         },
         TimerStatus {
             scope: "system".to_string(),
@@ -110,6 +235,10 @@ fn system_timers() -> Vec<TimerStatus> {
             trigger_unit: Some("stale.service".to_string()),
             persistent: None,
             result: None,
+            calendar: Vec::new(),
+            monotonic: Vec::new(),
+            accuracy_usec: None,
+            randomized_delay_usec: None,
         },
         TimerStatus {
             scope: "system".to_string(),
@@ -123,6 +252,14 @@ fn system_timers() -> Vec<TimerStatus> {
             trigger_unit: Some("overdue.service".to_string()),
             persistent: Some(true),
             result: Some("success".to_string()),
+            calendar: vec![TimerCalendarSpec {
+                base: "OnCalendar".to_string(),
+                expression: "*-*-* *:00:00 UTC".to_string(),
+                next_elapse_utc: Some("2020-01-01T00:00:00.000Z".to_string()),
+            }],
+            monotonic: Vec::new(),
+            accuracy_usec: Some(60_000_000),
+            randomized_delay_usec: Some(0),
         },
     ]
 }
@@ -140,9 +277,63 @@ fn user_timers() -> Vec<TimerStatus> {
         trigger_unit: Some("user-sync.service".to_string()),
         persistent: Some(false),
         result: Some("success".to_string()),
+        calendar: Vec::new(),
+        monotonic: vec![TimerMonotonicSpec {
+            base: "OnUnitActiveUSec".to_string(),
+            offset_usec: 86_400_000_000,
+        }],
+        accuracy_usec: Some(60_000_000),
+        randomized_delay_usec: Some(0),
+    }]
+}
+
+fn system_mounts() -> Vec<UnitInfo> {
+    vec![UnitInfo {
+        scope: "system".to_string(),
+        unit: "data.mount".to_string(),
+        unit_type: "mount".to_string(),
+        description: "Data volume".to_string(),
+        load_state: "loaded".to_string(),
+        active_state: "failed".to_string(),
+        sub_state: "failed".to_string(),
+        unit_file_state: Some("generated".to_string()),
+        since_utc: None,
+        details: Some(UnitTypeDetails::Mount {
+            what: Some("/dev/sdb1".to_string()),
+            mount_where: Some("/data".to_string()),
+        }),
     }]
 }
 
+/// Returns `(relationship, unit)` fixture edges forming the ordering cycle
+/// `z -> a -> b -> z` and the legal `b part_of z` requirement loop, plus a
+/// failed mount and a missing wanted unit.
+fn system_dependency_edges(unit: &str, direction: DependencyDirection) -> Vec<(String, String)> {
+    let edges: &[(&str, &str)] = match (direction, unit) {
+        (DependencyDirection::Forward, "z.service") => &[
+            ("requires", "a.service"),
+            ("wants", "data.mount"),
+            ("after", "a.service"),
+            ("after", "data.mount"),
+        ],
+        (DependencyDirection::Forward, "a.service") => {
+            &[("requires", "b.service"), ("after", "b.service")]
+        }
+        (DependencyDirection::Forward, "b.service") => &[
+            ("part_of", "z.service"),
+            ("after", "z.service"),
+            ("wants", "ghost.service"),
+        ],
+        (DependencyDirection::Reverse, "b.service") => &[("required_by", "a.service")],
+        (DependencyDirection::Reverse, "a.service") => &[("required_by", "z.service")],
+        _ => &[],
+    };
+    edges
+        .iter()
+        .map(|(relationship, target)| (relationship.to_string(), target.to_string()))
+        .collect()
+}
+
 fn system_logs() -> Vec<JournalLogEntry> {
     vec![
         JournalLogEntry {
@@ -153,6 +344,8 @@ fn system_logs() -> Vec<JournalLogEntry> {
             pid: Some(2222),
             message: Some("Started OpenSSH server".to_string()),
             cursor: Some("s=cursor;i=12".to_string()),
+            owner_uid: None,
+            machine: None,
         },
         JournalLogEntry {
             timestamp_utc: "2026-02-27T00:30:00.000Z".to_string(),
@@ -162,6 +355,8 @@ fn system_logs() -> Vec<JournalLogEntry> {
             pid: Some(3333),
             message: Some("Cron wake-up".to_string()),
             cursor: Some("s=cursor;i=13".to_string()),
+            owner_uid: None,
+            machine: None,
         },
         JournalLogEntry {
             timestamp_utc: "2026-02-27T00:45:00.000Z".to_string(),
@@ -171,6 +366,8 @@ fn system_logs() -> Vec<JournalLogEntry> {
             pid: Some(4444),
             message: Some("Application warning".to_string()),
             cursor: Some("s=cursor;i=14".to_string()),
+            owner_uid: None,
+            machine: None,
         },
     ]
 }
@@ -184,15 +381,41 @@ fn user_logs() -> Vec<JournalLogEntry> {
         pid: Some(5555),
         message: Some("User agent heartbeat".to_string()),
         cursor: Some("s=cursor;i=21".to_string()),
+        owner_uid: None,
+        machine: None,
     }]
 }
 
+/// Emulates a machine allowlist of `[web-1]` for machine scopes of the mocks.
+fn mock_machine_scope_tag(scope: UnitScope) -> Result<String, crate::errors::AppError> {
+    match scope {
+        UnitScope::Machine(name) if name.as_str() == "web-1" => Ok(scope.as_str().into_owned()),
+        _ => Err(crate::errors::AppError::bad_request(
+            "machine_not_allowed",
+            "machine scope is not in the configured allowlist",
+        )),
+    }
+}
+
+/// Emulates a UID allowlist of `[1000]` for per-user scopes of the mocks.
+fn mock_user_scope_tag(scope: UnitScope) -> Result<String, crate::errors::AppError> {
+    match scope {
+        UnitScope::UserId(1000) | UnitScope::AllUsers => Ok("user:1000".to_string()),
+        _ => Err(crate::errors::AppError::bad_request(
+            "user_not_allowed",
+            "user scope is not in the configured allowlist",
+        )),
+    }
+}
+
 #[async_trait::async_trait]
 impl UnitProvider for MockProvider {
     async fn system_state(&self, scope: UnitScope) -> Result<String, crate::errors::AppError> {
         match scope {
-            UnitScope::System | UnitScope::User => Ok("running".to_string()),
-            UnitScope::Both => Err(crate::errors::AppError::internal(
+            UnitScope::System | UnitScope::User | UnitScope::UserId(_) | UnitScope::Machine(_) => {
+                Ok("running".to_string())
+            }
+            UnitScope::Both | UnitScope::AllUsers => Err(crate::errors::AppError::internal(
                 "system_state requires a concrete scope",
             )),
         }
@@ -210,6 +433,26 @@ impl UnitProvider for MockProvider {
                 rows.extend(user_services());
                 rows
             }
+            UnitScope::UserId(_) | UnitScope::AllUsers => {
+                let tag = mock_user_scope_tag(scope)?;
+                user_services()
+                    .into_iter()
+                    .map(|row| UnitStatus {
+                        scope: tag.clone(),
+                        ..row
+                    })
+                    .collect()
+            }
+            UnitScope::Machine(_) => {
+                let tag = mock_machine_scope_tag(scope)?;
+                system_services()
+                    .into_iter()
+                    .map(|row| UnitStatus {
+                        scope: tag.clone(),
+                        ..row
+                    })
+                    .collect()
+            }
         })
     }
 
@@ -225,6 +468,27 @@ impl UnitProvider for MockProvider {
                 rows.extend(user_logs());
                 rows
             }
+            UnitScope::UserId(_) | UnitScope::AllUsers => {
+                mock_user_scope_tag(query.scope)?;
+                user_logs()
+                    .into_iter()
+                    .map(|entry| JournalLogEntry {
+                        owner_uid: Some(1000),
+                        ..entry
+                    })
+                    .collect()
+            }
+            UnitScope::Machine(name) => {
+                mock_machine_scope_tag(query.scope)?;
+                system_logs()
+                    .into_iter()
+                    .map(|entry| JournalLogEntry {
+                        hostname: Some(name.as_str().to_string()),
+                        machine: Some(name.as_str().to_string()),
+                        ..entry
+                    })
+                    .collect()
+            }
         };
 
         let scanned = entries.len();
@@ -272,16 +536,586 @@ impl UnitProvider for MockProvider {
                 rows.extend(user_timers());
                 rows
             }
+            UnitScope::UserId(_) | UnitScope::AllUsers => {
+                let tag = mock_user_scope_tag(scope)?;
+                user_timers()
+                    .into_iter()
+                    .map(|row| TimerStatus {
+                        scope: tag.clone(),
+                        ..row
+                    })
+                    .collect()
+            }
+            UnitScope::Machine(_) => {
+                let tag = mock_machine_scope_tag(scope)?;
+                system_timers()
+                    .into_iter()
+                    .map(|row| TimerStatus {
+                        scope: tag.clone(),
+                        ..row
+                    })
+                    .collect()
+            }
+        })
+    }
+    async fn list_units(
+        &self,
+        scope: UnitScope,
+        unit_type: Option<&str>,
+    ) -> Result<Vec<UnitInfo>, crate::errors::AppError> {
+        let mut units = Vec::new();
+        if scope != UnitScope::User {
+            units.extend(system_services().into_iter().map(UnitInfo::from));
+            units.extend(system_timers().into_iter().map(UnitInfo::from));
+            units.extend(system_mounts());
+        }
+        if scope != UnitScope::System {
+            units.extend(user_services().into_iter().map(UnitInfo::from));
+            units.extend(user_timers().into_iter().map(UnitInfo::from));
+        }
+        units.retain(|unit| unit_type.is_none_or(|expected| unit.unit_type == expected));
+        Ok(units)
+    }
+
+    async fn unit_dependency_edges(
+        &self,
+        unit: &str,
+        scope: UnitScope,
+        direction: DependencyDirection,
+    ) -> Result<Vec<(String, String)>, crate::errors::AppError> {
+        Ok(if scope == UnitScope::System {
+            system_dependency_edges(unit, direction)
+        } else {
+            Vec::new()
+        })
+    }
+
+    async fn unit_resources(
+        &self,
+        scope: UnitScope,
+        units: &[String],
+    ) -> Result<HashMap<String, UnitResources>, crate::errors::AppError> {
+        Ok(units
+            .iter()
+            .filter(|unit| scope == UnitScope::System && unit.as_str() == "z.service")
+            .map(|unit| {
+                (
+                    unit.clone(),
+                    UnitResources {
+                        memory_current_bytes: Some(52_428_800),
+                        memory_max_bytes: Some(268_435_456),
+                        cpu_usage_nsec: Some(1_500_000_000),
+                        tasks_current: Some(4),
+                        ..UnitResources::default()
+                    },
+                )
+            })
+            .collect())
+    }
+
+    async fn service_security_settings(
+        &self,
+        scope: UnitScope,
+        units: &[String],
+    ) -> Result<HashMap<String, ServiceSecuritySettings>, crate::errors::AppError> {
+        Ok(units
+            .iter()
+            .filter(|unit| scope == UnitScope::System && unit.ends_with(".service"))
+            .map(|unit| {
+                let settings = if unit == "z.service" {
+                    hardened_security_settings()
+                } else {
+                    unhardened_security_settings()
+                };
+                (unit.clone(), settings)
+            })
+            .collect())
+    }
+
+    async fn list_boots(
+        &self,
+        scope: UnitScope,
+    ) -> Result<Vec<BootRecord>, crate::errors::AppError> {
+        if scope == UnitScope::User {
+            return Ok(Vec::new());
+        }
+        let boot = |digit: &str, offset: i64, first: &str, last: &str| BootRecord {
+            boot_id: digit.repeat(32),
+            offset,
+            current: offset == 0,
+            first_utc: Some(first.to_string()),
+            last_utc: Some(last.to_string()),
+        };
+        Ok(vec![
+            boot(
+                "1",
+                -2,
+                "2026-02-10T08:00:00.000Z",
+                "2026-02-24T23:59:59.500Z",
+            ),
+            boot(
+                "2",
+                -1,
+                "2026-02-25T06:00:00.000Z",
+                "2026-02-26T22:10:00.250Z",
+            ),
+            boot(
+                "3",
+                0,
+                "2026-02-26T22:12:00.000Z",
+                "2026-02-27T01:00:00.000Z",
+            ),
+        ])
+    }
+
+    async fn list_login_sessions(&self) -> Result<LoginInventory, crate::errors::AppError> {
+        Ok(LoginInventory {
+            sessions: vec![
+                LoginSession {
+                    session_id: "2".to_string(),
+                    uid: 1000,
+                    user: "alice".to_string(),
+                    seat: Some("seat0".to_string()),
+                    class: Some("user".to_string()),
+                    session_type: Some("wayland".to_string()),
+                    state: Some("active".to_string()),
+                    remote: Some(false),
+                    remote_host: None,
+                    tty: Some("tty2".to_string()),
+                    idle_hint: Some(false),
+                    idle_since_utc: None,
+                    since_utc: Some("2026-02-26T22:15:00.000Z".to_string()),
+                },
+                LoginSession {
+                    session_id: "7".to_string(),
+                    uid: 1001,
+                    user: "bob".to_string(),
+                    seat: None,
+                    class: Some("user".to_string()),
+                    session_type: Some("tty".to_string()),
+                    state: Some("online".to_string()),
+                    remote: Some(true),
+                    remote_host: Some("192.0.2.10".to_string()),
+                    tty: Some("pts/0".to_string()),
+                    idle_hint: Some(true),
+                    idle_since_utc: Some("2026-02-27T00:30:00.000Z".to_string()),
+                    since_utc: Some("2026-02-27T00:10:00.000Z".to_string()),
+                },
+            ],
+            users: vec![
+                LoginUser {
+                    uid: 1000,
+                    user: "alice".to_string(),
+                    state: Some("active".to_string()),
+                    linger: Some(false),
+                    runtime_path: Some("/run/user/1000".to_string()),
+                    session_ids: vec!["2".to_string()],
+                },
+                LoginUser {
+                    uid: 1001,
+                    user: "bob".to_string(),
+                    state: Some("online".to_string()),
+                    linger: Some(false),
+                    runtime_path: Some("/run/user/1001".to_string()),
+                    session_ids: vec!["7".to_string()],
+                },
+                LoginUser {
+                    uid: 1002,
+                    user: "backup".to_string(),
+                    state: Some("lingering".to_string()),
+                    linger: Some(true),
+                    runtime_path: Some("/run/user/1002".to_string()),
+                    session_ids: Vec::new(),
+                },
+            ],
+            seats: vec![LoginSeat {
+                seat_id: "seat0".to_string(),
+                can_graphical: Some(true),
+                can_tty: Some(true),
+                session_ids: vec!["2".to_string()],
+            }],
+        })
+    }
+
+    async fn list_machines(
+        &self,
+        name: Option<&str>,
+    ) -> Result<Vec<MachineInfo>, crate::errors::AppError> {
+        let machines = vec![
+            MachineInfo {
+                name: "db".to_string(),
+                class: Some("vm".to_string()),
+                service: Some("libvirt-qemu".to_string()),
+                leader_pid: Some(3100),
+                root_directory: None,
+                network_interfaces: Vec::new(),
+                state: Some("running".to_string()),
+                unit: Some("machine-qemu\\x2d1\\x2ddb.scope".to_string()),
+                since_utc: Some("2026-02-26T22:20:00.000Z".to_string()),
+            },
+            MachineInfo {
+                name: "web-1".to_string(),
+                class: Some("container".to_string()),
+                service: Some("systemd-nspawn".to_string()),
+                leader_pid: Some(4242),
+                root_directory: Some("/var/lib/machines/web-1".to_string()),
+                network_interfaces: vec![7],
+                state: Some("running".to_string()),
+                unit: Some("systemd-nspawn@web-1.service".to_string()),
+                since_utc: Some("2026-02-26T22:14:00.000Z".to_string()),
+            },
+        ];
+        match name {
+            Some(name) => machines
+                .into_iter()
+                .find(|machine| machine.name == name)
+                .map(|machine| vec![machine])
+                .ok_or_else(|| {
+                    crate::errors::AppError::bad_request(
+                        "machine_not_found",
+                        "machine was not found",
+                    )
+                }),
+            None => Ok(machines),
+        }
+    }
+
+    async fn list_jobs(&self, scope: UnitScope) -> Result<Vec<JobInfo>, crate::errors::AppError> {
+        match scope {
+            UnitScope::System | UnitScope::Both => Ok(vec![
+                JobInfo {
+                    job_id: 812,
+                    scope: "system".to_string(),
+                    unit: "a.service".to_string(),
+                    job_type: "start".to_string(),
+                    state: "waiting".to_string(),
+                    first_seen_utc: "2026-02-27T00:00:00.000Z".to_string(),
+                    observed_for_ms: Some(95_000),
+                    waiting_on: vec![JobReference {
+                        job_id: 811,
+                        unit: "network-online.target".to_string(),
+                        job_type: "start".to_string(),
+                    }],
+                },
+                JobInfo {
+                    job_id: 811,
+                    scope: "system".to_string(),
+                    unit: "network-online.target".to_string(),
+                    job_type: "start".to_string(),
+                    state: "running".to_string(),
+                    first_seen_utc: "2026-02-27T00:00:00.000Z".to_string(),
+                    observed_for_ms: Some(95_000),
+                    waiting_on: Vec::new(),
+                },
+            ]),
+            UnitScope::User => Ok(Vec::new()),
+            UnitScope::UserId(_) | UnitScope::AllUsers => {
+                mock_user_scope_tag(scope)?;
+                Ok(Vec::new())
+            }
+            UnitScope::Machine(_) => {
+                mock_machine_scope_tag(scope)?;
+                Ok(Vec::new())
+            }
+        }
+    }
+
+    async fn list_unit_runs(
+        &self,
+        unit: &str,
+        scope: UnitScope,
+        _start_utc: chrono::DateTime<chrono::Utc>,
+        _end_utc: chrono::DateTime<chrono::Utc>,
+    ) -> Result<UnitRunHistory, crate::errors::AppError> {
+        if scope != UnitScope::System || unit != "backup.service" {
+            return Ok(UnitRunHistory {
+                runs: Vec::new(),
+                truncated: false,
+            });
+        }
+        let run = |started: &str, finished: Option<&str>, duration_ms: Option<u64>| UnitRun {
+            started_utc: started.to_string(),
+            finished_utc: finished.map(str::to_string),
+            duration_ms,
+            outcome: "success".to_string(),
+            result: Some("success".to_string()),
+            exit_code: Some("exited".to_string()),
+            exit_status: Some("0".to_string()),
+        };
+        let runs = vec![
+            run(
+                "2026-02-25T02:00:00.000Z",
+                Some("2026-02-25T02:10:00.000Z"),
+                Some(600_000),
+            ),
+            UnitRun {
+                outcome: "failed".to_string(),
+                result: Some("exit-code".to_string()),
+                exit_status: Some("2".to_string()),
+                ..run(
+                    "2026-02-26T02:00:00.000Z",
+                    Some("2026-02-26T02:00:30.000Z"),
+                    Some(30_000),
+                )
+            },
+            run(
+                "2026-02-27T02:00:00.000Z",
+                Some("2026-02-27T02:12:00.000Z"),
+                Some(720_000),
+            ),
+        ];
+        // The journal held older runs than the fixture returns.
+        Ok(UnitRunHistory {
+            runs,
+            truncated: true,
+        })
+    }
+
+    async fn list_coredumps(
+        &self,
+        query: &LogQuery,
+        executable: Option<&str>,
+    ) -> Result<Vec<CoredumpInfo>, crate::errors::AppError> {
+        if query.scope != UnitScope::System {
+            return Ok(Vec::new());
+        }
+        let coredump = |timestamp: &str, executable: &str, unit: &str, signal: &str| CoredumpInfo {
+            timestamp_utc: timestamp.to_string(),
+            executable: Some(executable.to_string()),
+            command: executable.rsplit('/').next().map(str::to_string),
+            pid: Some(4242),
+            uid: Some(0),
+            signal: Some(signal.to_string()),
+            unit: Some(unit.to_string()),
+            boot_id: Some("0123456789abcdef0123456789abcdef".to_string()),
+            core_stored: false,
+            stack_trace: Vec::new(),
+            stack_trace_truncated: false,
+        };
+        let mut coredumps = vec![
+            CoredumpInfo {
+                core_stored: true,
+                stack_trace: vec![
+                    "#0  0x00007f0000001000 crash (libcrash.so.1 + 0x1000)".to_string(),
+                ],
+                ..coredump(
+                    "2026-02-27T12:00:00.000Z",
+                    "/usr/bin/crashy",
+                    "a.service",
+                    "SIGSEGV",
+                )
+            },
+            coredump(
+                "2026-02-26T12:00:00.000Z",
+                "/usr/bin/aborty",
+                "b.service",
+                "SIGABRT",
+            ),
+            coredump(
+                "2026-02-25T12:00:00.000Z",
+                "/usr/bin/crashy",
+                "a.service",
+                "SIGSEGV",
+            ),
+        ];
+        coredumps.retain(|row| {
+            query
+                .unit
+                .as_deref()
+                .is_none_or(|unit| row.unit.as_deref() == Some(unit))
+                && executable.is_none_or(|path| row.executable.as_deref() == Some(path))
+        });
+        if query.order == LogOrder::Asc {
+            coredumps.reverse();
+        }
+        coredumps.truncate(query.limit + 1);
+        Ok(coredumps)
+    }
+
+    async fn storage_status(&self) -> Result<StorageReport, crate::errors::AppError> {
+        let mount = |mount_point: &str, fs_type: &str, used: u64, available: u64| MountUsage {
+            mount_point: mount_point.to_string(),
+            source: Some("/dev/sda1".to_string()),
+            fs_type: fs_type.to_string(),
+            read_only: false,
+            size_bytes: used + available,
+            used_bytes: used,
+            available_bytes: available,
+            inodes_total: 1_000,
+            inodes_used: 100,
+            inodes_free: 900,
+            stat_timed_out: false,
+        };
+        Ok(StorageReport {
+            mounts: vec![
+                mount("/var", "xfs", 95 * 1_073_741_824, 5 * 1_073_741_824),
+                mount("/", "ext4", 40 * 1_073_741_824, 60 * 1_073_741_824),
+                MountUsage {
+                    source: Some("/dev/sdb1".to_string()),
+                    inodes_used: 960,
+                    inodes_free: 40,
+                    ..mount("/data", "ext4", 1_073_741_824, 9 * 1_073_741_824)
+                },
+                MountUsage {
+                    source: Some("nas:/export".to_string()),
+                    size_bytes: 0,
+                    used_bytes: 0,
+                    available_bytes: 0,
+                    inodes_total: 0,
+                    inodes_used: 0,
+                    inodes_free: 0,
+                    stat_timed_out: true,
+                    ..mount("/mnt/nas", "nfs4", 0, 0)
+                },
+            ],
+            journal_directories: vec![
+                JournalDirectoryUsage {
+                    path: "/run/log/journal/0123456789abcdef0123456789abcdef".to_string(),
+                    persistent: false,
+                    files: 1,
+                    bytes: 8_388_608,
+                    truncated: false,
+                },
+                JournalDirectoryUsage {
+                    path: "/var/log/journal/0123456789abcdef0123456789abcdef".to_string(),
+                    persistent: true,
+                    files: 4,
+                    bytes: 536_870_912,
+                    truncated: false,
+                },
+            ],
         })
     }
+
+    async fn host_overview(&self) -> Result<HostOverview, crate::errors::AppError> {
+        Ok(HostOverview {
+            manager: ManagerOverview {
+                version: Some("257.4".to_string()),
+                system_state: Some("degraded".to_string()),
+                virtualization: Some("kvm".to_string()),
+                architecture: Some("x86-64".to_string()),
+                failed_units: Some(1),
+                jobs: Some(0),
+            },
+            host: Some(HostIdentity {
+                hostname: Some("test-host".to_string()),
+                static_hostname: Some("test-host".to_string()),
+                pretty_hostname: None,
+                chassis: Some("vm".to_string()),
+                operating_system: Some("Debian GNU/Linux 13 (trixie)".to_string()),
+                kernel_name: Some("Linux".to_string()),
+                kernel_release: Some("6.12.0-1-amd64".to_string()),
+            }),
+            time: Some(TimeSettings {
+                timezone: Some("Etc/UTC".to_string()),
+                local_rtc: Some(false),
+                ntp_enabled: Some(true),
+                ntp_synchronized: Some(true),
+            }),
+            uptime_seconds: Some(86_400),
+            booted_at_utc: Some("2026-02-27T00:00:00Z".to_string()),
+            load_average: Some(LoadAverage {
+                one_minute: 0.5,
+                five_minutes: 0.25,
+                fifteen_minutes: 0.125,
+            }),
+            memory: Some(MemoryTotals {
+                memory_total_bytes: Some(8_589_934_592),
+                memory_available_bytes: Some(4_294_967_296),
+                swap_total_bytes: Some(0),
+                swap_free_bytes: Some(0),
+            }),
+        })
+    }
+
+    async fn boot_timing(&self, scope: UnitScope) -> Result<BootTiming, crate::errors::AppError> {
+        if scope != UnitScope::System {
+            return Ok(BootTiming::default());
+        }
+        let activation =
+            |unit: &str, activating: Option<u64>, activated: u64, after: &[&str]| UnitActivation {
+                unit: unit.to_string(),
+                activating_usec: activating,
+                activated_usec: activated,
+                after: after.iter().map(|unit| unit.to_string()).collect(),
+            };
+        Ok(BootTiming {
+            firmware_usec: Some(5_000_000),
+            loader_usec: Some(2_000_000),
+            kernel_timestamp_usec: Some(1_767_258_000_000_000),
+            initrd_usec: Some(1_500_000),
+            userspace_usec: Some(4_000_000),
+            finish_usec: Some(14_000_000),
+            default_target: Some("graphical.target".to_string()),
+            units: vec![
+                activation("sysinit.target", Some(4_500_000), 4_900_000, &[]),
+                activation(
+                    "basic.target",
+                    Some(4_900_000),
+                    5_000_000,
+                    &["sysinit.target"],
+                ),
+                activation("z.service", Some(5_000_000), 11_000_000, &["basic.target"]),
+                activation("a.service", Some(5_100_000), 6_100_000, &["basic.target"]),
+                activation(
+                    "multi-user.target",
+                    Some(11_000_000),
+                    11_000_000,
+                    &["a.service", "basic.target", "z.service", "ghost.service"],
+                ),
+                activation(
+                    "graphical.target",
+                    Some(11_000_000),
+                    11_050_000,
+                    &["multi-user.target"],
+                ),
+                activation("late.service", Some(15_000_000), 16_000_000, &[]),
+            ],
+        })
+    }
+
+    async fn get_unit_file(
+        &self,
+        unit: &str,
+        scope: UnitScope,
+    ) -> Result<UnitFileView, crate::errors::AppError> {
+        if unit != "z.service" {
+            return Err(crate::errors::AppError::bad_request(
+                "unit_not_found",
+                "systemd unit was not found",
+            ));
+        }
+        let fragment = "/usr/lib/systemd/system/z.service".to_string();
+        let drop_in = "/etc/systemd/system/z.service.d/override.conf".to_string();
+        Ok(build_unit_file_view(
+            unit,
+            scope,
+            UnitFileSources {
+                fragment_path: Some(fragment.clone()),
+                drop_in_paths: vec![drop_in.clone()],
+                source_path: None,
+                need_daemon_reload: Some(true),
+            },
+            &[
+                (
+                    fragment,
+                    "[Service]\nEnvironment=API_TOKEN=abc123\nRestart=no\n".to_string(),
+                ),
+                (drop_in, "[Service]\nRestart=on-failure\n".to_string()),
+            ],
+            Vec::new(),
+        ))
+    }
 }
 
 #[async_trait::async_trait]
 impl UnitProvider for DegradedProvider {
     async fn system_state(&self, scope: UnitScope) -> Result<String, crate::errors::AppError> {
         match scope {
-            UnitScope::System | UnitScope::User => Ok("degraded".to_string()),
-            UnitScope::Both => Err(crate::errors::AppError::internal(
+            UnitScope::System | UnitScope::User | UnitScope::UserId(_) | UnitScope::Machine(_) => {
+                Ok("degraded".to_string())
+            }
+            UnitScope::Both | UnitScope::AllUsers => Err(crate::errors::AppError::internal(
                 "system_state requires a concrete scope",
             )),
         }
@@ -294,6 +1128,28 @@ impl UnitProvider for DegradedProvider {
         Ok(Vec::new())
     }
 
+    /// Reports a system bus that keeps failing to connect.
+    async fn connection_health(&self) -> Vec<ConnectionHealth> {
+        vec![
+            ConnectionHealth {
+                scope: "system".to_string(),
+                connected: false,
+                connected_since_utc: None,
+                consecutive_failures: 2,
+                disconnects: 1,
+                retry_in_ms: Some(1500),
+            },
+            ConnectionHealth {
+                scope: "user".to_string(),
+                connected: true,
+                connected_since_utc: Some("2026-02-27T00:00:00.000Z".to_string()),
+                consecutive_failures: 0,
+                disconnects: 0,
+                retry_in_ms: None,
+            },
+        ]
+    }
+
     async fn list_journal_logs(
         &self,
         _query: &LogQuery,
@@ -317,8 +1173,10 @@ impl UnitProvider for DegradedProvider {
 impl UnitProvider for ScopeCollisionProvider {
     async fn system_state(&self, scope: UnitScope) -> Result<String, crate::errors::AppError> {
         match scope {
-            UnitScope::System | UnitScope::User => Ok("running".to_string()),
-            UnitScope::Both => Err(crate::errors::AppError::internal(
+            UnitScope::System | UnitScope::User | UnitScope::UserId(_) | UnitScope::Machine(_) => {
+                Ok("running".to_string())
+            }
+            UnitScope::Both | UnitScope::AllUsers => Err(crate::errors::AppError::internal(
                 "system_state requires a concrete scope",
             )),
         }
@@ -340,12 +1198,25 @@ impl UnitProvider for ScopeCollisionProvider {
             main_pid: None,
             exec_main_status: None,
             result: None,
+            restart_count: None,
+            start_limit: None,
+            flapping: false,
+            pending_job: None,
+            exit_code: None,
+            exit_signal: None,
+            exec_main_start_utc: None,
+            exec_main_exit_utc: None,
+            oom_killed: false,
+            oom_kill_source: None,
         };
 
         Ok(match scope {
             UnitScope::System => vec![row("system")],
             UnitScope::User => vec![row("user")],
             UnitScope::Both => vec![row("system"), row("user")],
+            UnitScope::UserId(uid) => vec![row(&format!("user:{uid}"))],
+            UnitScope::AllUsers => vec![row("user:1000"), row("user:1001")],
+            UnitScope::Machine(name) => vec![row(&format!("machine:{}", name.as_str()))],
         })
     }
 
@@ -376,12 +1247,19 @@ impl UnitProvider for ScopeCollisionProvider {
             trigger_unit: None,
             persistent: None,
             result: None,
+            calendar: Vec::new(),
+            monotonic: Vec::new(),
+            accuracy_usec: None,
+            randomized_delay_usec: None,
         };
 
         Ok(match scope {
             UnitScope::System => vec![row("system")],
             UnitScope::User => vec![row("user")],
             UnitScope::Both => vec![row("system"), row("user")],
+            UnitScope::UserId(uid) => vec![row(&format!("user:{uid}"))],
+            UnitScope::AllUsers => vec![row("user:1000"), row("user:1001")],
+            UnitScope::Machine(name) => vec![row(&format!("machine:{}", name.as_str()))],
         })
     }
 }
@@ -521,6 +1399,79 @@ async fn systemd_status_degraded_returns_service_unavailable() {
     assert_eq!(body_json["details"]["status"], "degraded");
 }
 
+#[tokio::test]
+async fn ready_requires_token() {
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .uri("/ready")
+                .method("GET")
+                .body(Body::empty())
+                .expect("request build"),
+        )
+        .await
+        .expect("request execution");
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn ready_is_successful_without_persistent_connections() {
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .uri("/ready")
+                .method("GET")
+                .header(header::AUTHORIZATION, "Bearer token-1234567890ab")
+                .body(Body::empty())
+                .expect("request build"),
+        )
+        .await
+        .expect("request execution");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("collect body")
+        .to_bytes();
+    let body_json: serde_json::Value = serde_json::from_slice(&body).expect("valid json response");
+    assert_eq!(body_json["status"], "ready");
+    assert_eq!(body_json["connections"], serde_json::json!([]));
+}
+
+#[tokio::test]
+async fn ready_returns_service_unavailable_while_system_bus_is_down() {
+    let response = app_with_provider(Arc::new(DegradedProvider))
+        .oneshot(
+            Request::builder()
+                .uri("/ready")
+                .method("GET")
+                .header(header::AUTHORIZATION, "Bearer token-1234567890ab")
+                .body(Body::empty())
+                .expect("request build"),
+        )
+        .await
+        .expect("request execution");
+
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("collect body")
+        .to_bytes();
+    let body_json: serde_json::Value = serde_json::from_slice(&body).expect("valid json response");
+    assert_eq!(body_json["code"], "not_ready");
+    let connections = &body_json["details"]["connections"];
+    assert_eq!(connections[0]["scope"], "system");
+    assert_eq!(connections[0]["connected"], false);
+    assert_eq!(connections[0]["consecutive_failures"], 2);
+    assert_eq!(connections[0]["retry_in_ms"], 1500);
+    assert_eq!(connections[1]["connected"], true);
+}
+
 #[tokio::test]
 async fn services_route_is_not_found() {
     let response = app()
@@ -810,7 +1761,10 @@ async fn mcp_tools_list_returns_required_tools() {
     let list_services_description = body_json["result"]["tools"][0]["description"]
         .as_str()
         .expect("list_services description");
-    assert!(list_services_description.contains("scope accepts system, user, or both"));
+    assert!(
+        list_services_description
+            .contains("scope accepts system, user, both, user:<uid>, users:all, or machine:<name>")
+    );
     assert!(list_services_description.contains("state accepts active"));
     assert!(list_services_description.contains("limit accepts 1-1000"));
     let list_timers_description = body_json["result"]["tools"][1]["description"]
@@ -829,8 +1783,10 @@ async fn mcp_tools_list_returns_required_tools() {
     assert!(list_logs_description.contains("start_utc is required unless since_last_start=true"));
     let list_logs_required = body_json["result"]["tools"][2]["inputSchema"]["required"]
         .as_array()
-        .expect("list_logs required fields");
-    assert!(list_logs_required.iter().any(|field| field == "end_utc"));
+        .cloned()
+        .unwrap_or_default();
+    assert!(list_logs_description.contains("end_utc is required unless boot is set"));
+    assert!(!list_logs_required.iter().any(|field| field == "end_utc"));
     assert!(!list_logs_required.iter().any(|field| field == "start_utc"));
 }
 
@@ -1384,6 +2340,148 @@ async fn mcp_tools_call_list_timers_scope_both_preserves_same_name_rows() {
     );
 }
 
+#[tokio::test]
+async fn mcp_tools_call_list_services_scope_all_users_tags_rows_with_owner() {
+    let body_json = call_tool(
+        app_with_provider(Arc::new(ScopeCollisionProvider)),
+        r#"{"jsonrpc":"2.0","id":434,"method":"tools/call","params":{"name":"list_services","arguments":{"scope":"users:all"}}}"#,
+    )
+    .await;
+    let services = body_json["result"]["structuredContent"]["services"]
+        .as_array()
+        .expect("services array");
+
+    assert_eq!(services.len(), 2);
+    assert_eq!(services[0]["scope"], "user:1000");
+    assert_eq!(services[1]["scope"], "user:1001");
+    assert!(services.iter().all(|row| row["unit"] == "shared.service"));
+}
+
+#[tokio::test]
+async fn mcp_tools_call_user_id_scope_reads_one_allowed_user() {
+    let body_json = call_tool(
+        app(),
+        r#"{"jsonrpc":"2.0","id":435,"method":"tools/call","params":{"name":"list_timers","arguments":{"scope":"user:1000"}}}"#,
+    )
+    .await;
+    let timers = body_json["result"]["structuredContent"]["timers"]
+        .as_array()
+        .expect("timers array");
+    assert_eq!(timers.len(), 1);
+    assert_eq!(timers[0]["unit"], "user-sync.timer");
+    assert_eq!(timers[0]["scope"], "user:1000");
+
+    let body_json = call_tool(
+        app(),
+        r#"{"jsonrpc":"2.0","id":436,"method":"tools/call","params":{"name":"list_logs","arguments":{"scope":"users:all","start_utc":"2026-02-27T00:00:00Z","end_utc":"2026-02-27T01:00:00Z","limit":10}}}"#,
+    )
+    .await;
+    let logs = &body_json["result"]["structuredContent"]["logs"];
+    assert_eq!(logs[0]["unit"], "user-agent.service");
+    assert_eq!(logs[0]["owner_uid"], 1000);
+}
+
+#[tokio::test]
+async fn mcp_tools_call_rejects_users_outside_allowlist_and_malformed_user_scopes() {
+    let body_json = call_tool(
+        app(),
+        r#"{"jsonrpc":"2.0","id":437,"method":"tools/call","params":{"name":"list_services","arguments":{"scope":"user:1001"}}}"#,
+    )
+    .await;
+    assert_eq!(body_json["error"]["code"], -32602);
+    assert_eq!(body_json["error"]["data"]["code"], "user_not_allowed");
+
+    for body in [
+        r#"{"jsonrpc":"2.0","id":438,"method":"tools/call","params":{"name":"list_services","arguments":{"scope":"user:alice"}}}"#,
+        r#"{"jsonrpc":"2.0","id":439,"method":"tools/call","params":{"name":"get_unit_status","arguments":{"unit":"ssh.service","scope":"users:all"}}}"#,
+    ] {
+        let body_json = call_tool(app(), body).await;
+        assert_eq!(body_json["error"]["code"], -32602, "{body}");
+        assert_eq!(
+            body_json["error"]["data"]["code"], "invalid_scope",
+            "{body}"
+        );
+    }
+
+    for tool in [
+        "get_unit_status",
+        "get_unit_file",
+        "get_unit_resources",
+        "get_unit_dependencies",
+        "analyze_unit_security",
+    ] {
+        let body = format!(
+            r#"{{"jsonrpc":"2.0","id":440,"method":"tools/call","params":{{"name":"{tool}","arguments":{{"unit":"ssh.service","scope":"both"}}}}}}"#
+        );
+        let body_json = call_tool(app(), &body).await;
+        assert_eq!(
+            body_json["error"]["data"]["code"], "invalid_scope",
+            "{tool}"
+        );
+        assert_eq!(
+            body_json["error"]["data"]["message"],
+            "scope must be one of: system, user, user:<uid>, machine:<name>",
+            "{tool}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn mcp_tools_call_machine_scope_tags_rows_with_machine() {
+    let body_json = call_tool(
+        app(),
+        r#"{"jsonrpc":"2.0","id":450,"method":"tools/call","params":{"name":"list_services","arguments":{"scope":"machine:web-1"}}}"#,
+    )
+    .await;
+    let services = body_json["result"]["structuredContent"]["services"]
+        .as_array()
+        .expect("services array");
+    assert!(!services.is_empty());
+    assert!(services.iter().all(|row| row["scope"] == "machine:web-1"));
+
+    let body_json = call_tool(
+        app(),
+        r#"{"jsonrpc":"2.0","id":451,"method":"tools/call","params":{"name":"list_timers","arguments":{"scope":"Machine:web-1"}}}"#,
+    )
+    .await;
+    let timers = body_json["result"]["structuredContent"]["timers"]
+        .as_array()
+        .expect("timers array");
+    assert_eq!(timers[0]["unit"], "backup.timer");
+    assert_eq!(timers[0]["scope"], "machine:web-1");
+
+    let body_json = call_tool(
+        app(),
+        r#"{"jsonrpc":"2.0","id":452,"method":"tools/call","params":{"name":"list_logs","arguments":{"scope":"machine:web-1","start_utc":"2026-02-27T00:00:00Z","end_utc":"2026-02-27T01:00:00Z","limit":10}}}"#,
+    )
+    .await;
+    let logs = body_json["result"]["structuredContent"]["logs"]
+        .as_array()
+        .expect("logs array");
+    assert_eq!(logs.len(), 3);
+    assert!(logs.iter().all(|entry| entry["machine"] == "web-1"));
+    assert!(logs.iter().all(|entry| entry.get("owner_uid").is_none()));
+}
+
+#[tokio::test]
+async fn mcp_tools_call_rejects_machines_outside_allowlist_and_malformed_machine_scopes() {
+    let body_json = call_tool(
+        app(),
+        r#"{"jsonrpc":"2.0","id":453,"method":"tools/call","params":{"name":"list_services","arguments":{"scope":"machine:db"}}}"#,
+    )
+    .await;
+    assert_eq!(body_json["error"]["code"], -32602);
+    assert_eq!(body_json["error"]["data"]["code"], "machine_not_allowed");
+
+    let body_json = call_tool(
+        app(),
+        r#"{"jsonrpc":"2.0","id":454,"method":"tools/call","params":{"name":"list_services","arguments":{"scope":"machine:../web"}}}"#,
+    )
+    .await;
+    assert_eq!(body_json["error"]["code"], -32602);
+    assert_eq!(body_json["error"]["data"]["code"], "invalid_scope");
+}
+
 #[tokio::test]
 async fn mcp_tools_call_list_logs_scope_user_filters_source() {
     let response = app()
@@ -2142,6 +3240,1933 @@ async fn mcp_tools_call_list_logs_since_last_start_rejects_invalid_scope() {
     assert_eq!(body_json["error"]["data"]["code"], "invalid_scope");
 }
 
+#[tokio::test]
+async fn mcp_tools_call_list_units_filters_by_type_with_mount_details() {
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .uri("/mcp")
+                .method("POST")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::AUTHORIZATION, "Bearer token-1234567890ab")
+                .body(Body::from(
+                    r#"{"jsonrpc":"2.0","id":401,"method":"tools/call","params":{"name":"list_units","arguments":{"type":"mount"}}}"#,
+                ))
+                .expect("request build"),
+        )
+        .await
+        .expect("request execution");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("collect body")
+        .to_bytes();
+    let body_json: serde_json::Value = serde_json::from_slice(&body).expect("valid json response");
+
+    let units = &body_json["result"]["structuredContent"]["units"];
+    assert_eq!(body_json["result"]["structuredContent"]["returned"], 1);
+    assert_eq!(units[0]["unit"], "data.mount");
+    assert_eq!(units[0]["unit_type"], "mount");
+    assert_eq!(units[0]["details"]["what"], "/dev/sdb1");
+    assert_eq!(units[0]["details"]["where"], "/data");
+}
+
+#[tokio::test]
+async fn mcp_tools_call_list_units_failed_state_spans_unit_types() {
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .uri("/mcp")
+                .method("POST")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::AUTHORIZATION, "Bearer token-1234567890ab")
+                .body(Body::from(
+                    r#"{"jsonrpc":"2.0","id":402,"method":"tools/call","params":{"name":"list_units","arguments":{"state":"FAILED"}}}"#,
+                ))
+                .expect("request build"),
+        )
+        .await
+        .expect("request execution");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("collect body")
+        .to_bytes();
+    let body_json: serde_json::Value = serde_json::from_slice(&body).expect("valid json response");
+
+    let units = body_json["result"]["structuredContent"]["units"]
+        .as_array()
+        .expect("units array");
+    let names = units
+        .iter()
+        .map(|unit| unit["unit"].as_str().expect("unit name"))
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["b.service", "data.mount"]);
+}
+
+#[tokio::test]
+async fn mcp_tools_call_list_units_summary_counts_types() {
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .uri("/mcp")
+                .method("POST")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::AUTHORIZATION, "Bearer token-1234567890ab")
+                .body(Body::from(
+                    r#"{"jsonrpc":"2.0","id":403,"method":"tools/call","params":{"name":"list_units","arguments":{"summary":true}}}"#,
+                ))
+                .expect("request build"),
+        )
+        .await
+        .expect("request execution");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("collect body")
+        .to_bytes();
+    let body_json: serde_json::Value = serde_json::from_slice(&body).expect("valid json response");
+
+    let summary = &body_json["result"]["structuredContent"]["summary"];
+    assert_eq!(summary["counts_by_type"]["service"], 3);
+    assert_eq!(summary["counts_by_type"]["timer"], 3);
+    assert_eq!(summary["counts_by_type"]["mount"], 1);
+    assert_eq!(summary["failed_units"][1]["unit_type"], "mount");
+    assert!(summary["degraded_hint"].is_string());
+    assert_eq!(body_json["result"]["structuredContent"]["total"], 7);
+}
+
+#[tokio::test]
+async fn mcp_tools_call_list_units_rejects_invalid_type() {
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .uri("/mcp")
+                .method("POST")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::AUTHORIZATION, "Bearer token-1234567890ab")
+                .body(Body::from(
+                    r#"{"jsonrpc":"2.0","id":404,"method":"tools/call","params":{"name":"list_units","arguments":{"type":"container"}}}"#,
+                ))
+                .expect("request build"),
+        )
+        .await
+        .expect("request execution");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("collect body")
+        .to_bytes();
+    let body_json: serde_json::Value = serde_json::from_slice(&body).expect("valid json response");
+
+    assert_eq!(body_json["error"]["code"], -32602);
+    assert_eq!(body_json["error"]["data"]["code"], "invalid_unit_type");
+}
+
+#[tokio::test]
+async fn mcp_tools_call_get_unit_status_inspects_non_service_unit() {
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .uri("/mcp")
+                .method("POST")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::AUTHORIZATION, "Bearer token-1234567890ab")
+                .body(Body::from(
+                    r#"{"jsonrpc":"2.0","id":405,"method":"tools/call","params":{"name":"get_unit_status","arguments":{"unit":"data.mount"}}}"#,
+                ))
+                .expect("request build"),
+        )
+        .await
+        .expect("request execution");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("collect body")
+        .to_bytes();
+    let body_json: serde_json::Value = serde_json::from_slice(&body).expect("valid json response");
+
+    let status = &body_json["result"]["structuredContent"]["status"];
+    assert_eq!(status["unit"], "data.mount");
+    assert_eq!(status["unit_type"], "mount");
+    assert_eq!(status["active_state"], "failed");
+    assert_eq!(status["details"]["where"], "/data");
+    assert!(status["failed_dependencies"].is_array());
+    assert!(status["recent_transitions"].is_array());
+}
+
+#[tokio::test]
+async fn mcp_tools_call_get_unit_status_rejects_unknown_unit_type() {
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .uri("/mcp")
+                .method("POST")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::AUTHORIZATION, "Bearer token-1234567890ab")
+                .body(Body::from(
+                    r#"{"jsonrpc":"2.0","id":406,"method":"tools/call","params":{"name":"get_unit_status","arguments":{"unit":"data.volume"}}}"#,
+                ))
+                .expect("request build"),
+        )
+        .await
+        .expect("request execution");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("collect body")
+        .to_bytes();
+    let body_json: serde_json::Value = serde_json::from_slice(&body).expect("valid json response");
+
+    assert_eq!(body_json["error"]["code"], -32602);
+    assert_eq!(body_json["error"]["data"]["code"], "invalid_unit");
+}
+
+#[tokio::test]
+async fn mcp_tools_call_get_unit_dependencies_traces_deepest_failure_and_cycles() {
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .uri("/mcp")
+                .method("POST")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::AUTHORIZATION, "Bearer token-1234567890ab")
+                .body(Body::from(
+                    r#"{"jsonrpc":"2.0","id":407,"method":"tools/call","params":{"name":"get_unit_dependencies","arguments":{"unit":"z.service"}}}"#,
+                ))
+                .expect("request build"),
+        )
+        .await
+        .expect("request execution");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("collect body")
+        .to_bytes();
+    let body_json: serde_json::Value = serde_json::from_slice(&body).expect("valid json response");
+
+    let graph = &body_json["result"]["structuredContent"];
+    assert_eq!(graph["root"], "z.service");
+    assert_eq!(graph["direction"], "forward");
+    assert_eq!(graph["truncated"], false);
+    let nodes = graph["nodes"].as_array().expect("nodes array");
+    assert_eq!(nodes.len(), 5);
+    assert_eq!(nodes[0]["unit"], "z.service");
+    assert_eq!(nodes[4]["unit"], "ghost.service");
+    assert_eq!(nodes[4]["load_state"], "not-found");
+    assert_eq!(nodes[4]["failed"], true);
+    assert_eq!(
+        graph["cycles"],
+        serde_json::json!([["z.service", "a.service", "b.service", "z.service"]])
+    );
+    let trace = graph["failed_trace"]
+        .as_array()
+        .expect("trace array")
+        .iter()
+        .map(|step| step["unit"].as_str().expect("trace unit"))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        trace,
+        vec!["z.service", "a.service", "b.service", "ghost.service"]
+    );
+    assert_eq!(
+        graph["failed_trace"][0]["relationship"],
+        serde_json::Value::Null
+    );
+    assert_eq!(graph["failed_trace"][3]["relationship"], "wants");
+}
+
+#[tokio::test]
+async fn mcp_tools_call_get_unit_dependencies_walks_reverse_with_bounds() {
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .uri("/mcp")
+                .method("POST")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::AUTHORIZATION, "Bearer token-1234567890ab")
+                .body(Body::from(
+                    r#"{"jsonrpc":"2.0","id":408,"method":"tools/call","params":{"name":"get_unit_dependencies","arguments":{"unit":"b.service","direction":"reverse","max_depth":1}}}"#,
+                ))
+                .expect("request build"),
+        )
+        .await
+        .expect("request execution");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("collect body")
+        .to_bytes();
+    let body_json: serde_json::Value = serde_json::from_slice(&body).expect("valid json response");
+
+    let graph = &body_json["result"]["structuredContent"];
+    assert_eq!(graph["direction"], "reverse");
+    assert_eq!(
+        graph["edges"],
+        serde_json::json!([{"from": "b.service", "to": "a.service", "relationship": "required_by"}])
+    );
+    assert_eq!(graph["nodes"].as_array().expect("nodes array").len(), 2);
+    assert_eq!(graph["failed_trace"], serde_json::json!([]));
+}
+
+#[tokio::test]
+async fn mcp_tools_call_get_unit_dependencies_rejects_invalid_direction() {
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .uri("/mcp")
+                .method("POST")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::AUTHORIZATION, "Bearer token-1234567890ab")
+                .body(Body::from(
+                    r#"{"jsonrpc":"2.0","id":409,"method":"tools/call","params":{"name":"get_unit_dependencies","arguments":{"unit":"z.service","direction":"sideways"}}}"#,
+                ))
+                .expect("request build"),
+        )
+        .await
+        .expect("request execution");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("collect body")
+        .to_bytes();
+    let body_json: serde_json::Value = serde_json::from_slice(&body).expect("valid json response");
+
+    assert_eq!(body_json["error"]["code"], -32602);
+    assert_eq!(body_json["error"]["data"]["code"], "invalid_direction");
+}
+
+#[tokio::test]
+async fn mcp_tools_call_get_unit_file_redacts_environment_and_tracks_overrides() {
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .uri("/mcp")
+                .method("POST")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::AUTHORIZATION, "Bearer token-1234567890ab")
+                .body(Body::from(
+                    r#"{"jsonrpc":"2.0","id":410,"method":"tools/call","params":{"name":"get_unit_file","arguments":{"unit":"z.service"}}}"#,
+                ))
+                .expect("request build"),
+        )
+        .await
+        .expect("request execution");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("collect body")
+        .to_bytes();
+    let body_text = String::from_utf8(body.to_vec()).expect("utf8 body");
+    assert!(!body_text.contains("abc123"));
+    let body_json: serde_json::Value =
+        serde_json::from_str(&body_text).expect("valid json response");
+
+    let view = &body_json["result"]["structuredContent"];
+    assert_eq!(view["need_daemon_reload"], true);
+    let entries = &view["sections"][0]["entries"];
+    assert_eq!(entries[0]["key"], "Environment");
+    assert_eq!(entries[0]["value"], "[REDACTED]");
+    assert_eq!(entries[0]["redacted"], true);
+    assert_eq!(
+        entries[1]["overridden_by"],
+        "/etc/systemd/system/z.service.d/override.conf"
+    );
+    assert_eq!(entries[2]["value"], "on-failure");
+}
+
+#[tokio::test]
+async fn mcp_tools_call_get_unit_file_rejects_untyped_unit() {
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .uri("/mcp")
+                .method("POST")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::AUTHORIZATION, "Bearer token-1234567890ab")
+                .body(Body::from(
+                    r#"{"jsonrpc":"2.0","id":411,"method":"tools/call","params":{"name":"get_unit_file","arguments":{"unit":"../etc/shadow"}}}"#,
+                ))
+                .expect("request build"),
+        )
+        .await
+        .expect("request execution");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("collect body")
+        .to_bytes();
+    let body_json: serde_json::Value = serde_json::from_slice(&body).expect("valid json response");
+
+    assert_eq!(body_json["error"]["code"], -32602);
+    assert_eq!(body_json["error"]["data"]["code"], "invalid_unit");
+}
+
+#[tokio::test]
+async fn mcp_tools_call_get_unit_resources_returns_counters_and_limits() {
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .uri("/mcp")
+                .method("POST")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::AUTHORIZATION, "Bearer token-1234567890ab")
+                .body(Body::from(
+                    r#"{"jsonrpc":"2.0","id":412,"method":"tools/call","params":{"name":"get_unit_resources","arguments":{"unit":"z.service"}}}"#,
+                ))
+                .expect("request build"),
+        )
+        .await
+        .expect("request execution");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("collect body")
+        .to_bytes();
+    let body_json: serde_json::Value = serde_json::from_slice(&body).expect("valid json response");
+
+    let payload = &body_json["result"]["structuredContent"];
+    assert_eq!(payload["unit"], "z.service");
+    assert_eq!(payload["resources"]["memory_current_bytes"], 52_428_800);
+    assert_eq!(payload["resources"]["memory_max_bytes"], 268_435_456);
+    assert_eq!(payload["resources"]["tasks_max"], serde_json::Value::Null);
+}
+
+#[tokio::test]
+async fn mcp_tools_call_get_unit_resources_rejects_unit_without_cgroup() {
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .uri("/mcp")
+                .method("POST")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::AUTHORIZATION, "Bearer token-1234567890ab")
+                .body(Body::from(
+                    r#"{"jsonrpc":"2.0","id":413,"method":"tools/call","params":{"name":"get_unit_resources","arguments":{"unit":"backup.timer"}}}"#,
+                ))
+                .expect("request build"),
+        )
+        .await
+        .expect("request execution");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("collect body")
+        .to_bytes();
+    let body_json: serde_json::Value = serde_json::from_slice(&body).expect("valid json response");
+
+    assert_eq!(body_json["error"]["code"], -32602);
+    assert_eq!(body_json["error"]["data"]["code"], "invalid_unit");
+}
+
+#[tokio::test]
+async fn mcp_tools_call_list_services_include_resources_adds_per_row_resources() {
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .uri("/mcp")
+                .method("POST")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::AUTHORIZATION, "Bearer token-1234567890ab")
+                .body(Body::from(
+                    r#"{"jsonrpc":"2.0","id":414,"method":"tools/call","params":{"name":"list_services","arguments":{"include_resources":true}}}"#,
+                ))
+                .expect("request build"),
+        )
+        .await
+        .expect("request execution");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("collect body")
+        .to_bytes();
+    let body_json: serde_json::Value = serde_json::from_slice(&body).expect("valid json response");
+
+    let services = body_json["result"]["structuredContent"]["services"]
+        .as_array()
+        .expect("services array");
+    let z_service = services
+        .iter()
+        .find(|service| service["unit"] == "z.service")
+        .expect("z.service row");
+    assert_eq!(z_service["resources"]["cpu_usage_nsec"], 1_500_000_000_u64);
+    let a_service = services
+        .iter()
+        .find(|service| service["unit"] == "a.service")
+        .expect("a.service row");
+    assert!(a_service["resources"].is_null());
+    assert!(
+        a_service
+            .as_object()
+            .expect("row object")
+            .contains_key("resources")
+    );
+}
+
+#[tokio::test]
+async fn mcp_tools_call_list_services_omits_resources_by_default() {
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .uri("/mcp")
+                .method("POST")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::AUTHORIZATION, "Bearer token-1234567890ab")
+                .body(Body::from(
+                    r#"{"jsonrpc":"2.0","id":415,"method":"tools/call","params":{"name":"list_services","arguments":{}}}"#,
+                ))
+                .expect("request build"),
+        )
+        .await
+        .expect("request execution");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("collect body")
+        .to_bytes();
+    let body_json: serde_json::Value = serde_json::from_slice(&body).expect("valid json response");
+
+    let services = body_json["result"]["structuredContent"]["services"]
+        .as_array()
+        .expect("services array");
+    assert!(services.iter().all(|service| {
+        !service
+            .as_object()
+            .expect("row object")
+            .contains_key("resources")
+    }));
+}
+
+#[tokio::test]
+async fn mcp_tools_call_top_units_ranks_running_services_with_scope() {
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .uri("/mcp")
+                .method("POST")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::AUTHORIZATION, "Bearer token-1234567890ab")
+                .body(Body::from(
+                    r#"{"jsonrpc":"2.0","id":416,"method":"tools/call","params":{"name":"top_units","arguments":{"metric":"memory","interval_ms":100}}}"#,
+                ))
+                .expect("request build"),
+        )
+        .await
+        .expect("request execution");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("collect body")
+        .to_bytes();
+    let body_json: serde_json::Value = serde_json::from_slice(&body).expect("valid json response");
+
+    let payload = &body_json["result"]["structuredContent"];
+    assert_eq!(payload["metric"], "memory");
+    let units = payload["units"].as_array().expect("units array");
+    assert_eq!(units[0]["unit"], "z.service");
+    assert_eq!(units[0]["scope"], "system");
+    assert_eq!(units[0]["memory_current_bytes"], 52_428_800);
+    assert_eq!(units[0]["cpu_percent"], 0.0);
+    assert!(
+        units
+            .iter()
+            .all(|unit| unit["unit"] != "a.service" && unit["unit"] != "b.service")
+    );
+}
+
+#[tokio::test]
+async fn mcp_tools_call_top_units_rejects_unknown_metric() {
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .uri("/mcp")
+                .method("POST")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::AUTHORIZATION, "Bearer token-1234567890ab")
+                .body(Body::from(
+                    r#"{"jsonrpc":"2.0","id":417,"method":"tools/call","params":{"name":"top_units","arguments":{"metric":"disk"}}}"#,
+                ))
+                .expect("request build"),
+        )
+        .await
+        .expect("request execution");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("collect body")
+        .to_bytes();
+    let body_json: serde_json::Value = serde_json::from_slice(&body).expect("valid json response");
+
+    assert_eq!(body_json["error"]["code"], -32602);
+    assert_eq!(body_json["error"]["data"]["code"], "invalid_metric");
+}
+
+#[tokio::test]
+async fn mcp_tools_call_analyze_unit_security_reports_weighted_findings() {
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .uri("/mcp")
+                .method("POST")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::AUTHORIZATION, "Bearer token-1234567890ab")
+                .body(Body::from(
+                    r#"{"jsonrpc":"2.0","id":418,"method":"tools/call","params":{"name":"analyze_unit_security","arguments":{"unit":"a.service"}}}"#,
+                ))
+                .expect("request build"),
+        )
+        .await
+        .expect("request execution");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("collect body")
+        .to_bytes();
+    let body_json: serde_json::Value = serde_json::from_slice(&body).expect("valid json response");
+
+    let payload = &body_json["result"]["structuredContent"];
+    assert_eq!(payload["unit"], "a.service");
+    assert_eq!(payload["scope"], "system");
+    assert_eq!(payload["rating"], "unsafe");
+    let checks = payload["checks"].as_array().expect("checks array");
+    assert_eq!(checks.len(), 24);
+    assert_eq!(checks[0]["id"], "user");
+    assert_eq!(checks[0]["status"], "exposed");
+    assert!(checks.iter().all(|check| check.get("outcome").is_none()));
+    let findings = payload["findings"].as_array().expect("findings array");
+    assert!(
+        findings
+            .iter()
+            .any(|finding| finding["id"] == "capability_bounding_set"
+                && finding["value"] == "includes CAP_SYS_ADMIN")
+    );
+    assert!(
+        findings
+            .iter()
+            .all(|finding| finding["id"] != "ambient_capabilities")
+    );
+}
+
+#[tokio::test]
+async fn mcp_tools_call_analyze_unit_security_ranks_services_host_wide() {
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .uri("/mcp")
+                .method("POST")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::AUTHORIZATION, "Bearer token-1234567890ab")
+                .body(Body::from(
+                    r#"{"jsonrpc":"2.0","id":419,"method":"tools/call","params":{"name":"analyze_unit_security","arguments":{}}}"#,
+                ))
+                .expect("request build"),
+        )
+        .await
+        .expect("request execution");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("collect body")
+        .to_bytes();
+    let body_json: serde_json::Value = serde_json::from_slice(&body).expect("valid json response");
+
+    let payload = &body_json["result"]["structuredContent"];
+    assert_eq!(payload["total"], 3);
+    let services = payload["services"].as_array().expect("services array");
+    assert_eq!(services[0]["unit"], "a.service");
+    assert_eq!(services[1]["unit"], "b.service");
+    assert_eq!(services[2]["unit"], "z.service");
+    assert_eq!(services[2]["rating"], "safe");
+    assert_eq!(services[2]["finding_count"], 1);
+    assert!(services[0]["exposure"].as_f64() > services[2]["exposure"].as_f64());
+}
+
+#[tokio::test]
+async fn mcp_tools_call_analyze_unit_security_rejects_non_service_unit() {
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .uri("/mcp")
+                .method("POST")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::AUTHORIZATION, "Bearer token-1234567890ab")
+                .body(Body::from(
+                    r#"{"jsonrpc":"2.0","id":420,"method":"tools/call","params":{"name":"analyze_unit_security","arguments":{"unit":"backup.timer"}}}"#,
+                ))
+                .expect("request build"),
+        )
+        .await
+        .expect("request execution");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("collect body")
+        .to_bytes();
+    let body_json: serde_json::Value = serde_json::from_slice(&body).expect("valid json response");
+
+    assert_eq!(body_json["error"]["code"], -32602);
+    assert_eq!(body_json["error"]["data"]["code"], "invalid_unit");
+}
+
+#[tokio::test]
+async fn mcp_tools_call_get_boot_timeline_returns_phases_blame_and_critical_chain() {
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .uri("/mcp")
+                .method("POST")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::AUTHORIZATION, "Bearer token-1234567890ab")
+                .body(Body::from(
+                    r#"{"jsonrpc":"2.0","id":421,"method":"tools/call","params":{"name":"get_boot_timeline","arguments":{"limit":2}}}"#,
+                ))
+                .expect("request build"),
+        )
+        .await
+        .expect("request execution");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("collect body")
+        .to_bytes();
+    let body_json: serde_json::Value = serde_json::from_slice(&body).expect("valid json response");
+
+    let payload = &body_json["result"]["structuredContent"];
+    assert_eq!(payload["target"], "graphical.target");
+    assert_eq!(payload["finished"], true);
+    assert_eq!(payload["firmware_usec"], 3_000_000);
+    assert_eq!(payload["loader_usec"], 2_000_000);
+    assert_eq!(payload["kernel_usec"], 1_500_000);
+    assert_eq!(payload["initrd_usec"], 2_500_000);
+    assert_eq!(payload["userspace_usec"], 10_000_000);
+    assert_eq!(payload["total_usec"], 19_000_000);
+    assert_eq!(payload["blame_total"], 6);
+    let blame = payload["blame"].as_array().expect("blame array");
+    assert_eq!(blame.len(), 2);
+    assert_eq!(blame[0]["unit"], "z.service");
+    assert_eq!(blame[0]["activation_usec"], 6_000_000);
+    let chain = payload["critical_chain"]
+        .as_array()
+        .expect("critical chain array")
+        .iter()
+        .map(|link| link["unit"].as_str().expect("unit").to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        chain,
+        [
+            "graphical.target",
+            "multi-user.target",
+            "z.service",
+            "basic.target",
+            "sysinit.target"
+        ]
+    );
+    assert_eq!(payload["critical_chain"][2]["activated_at_usec"], 7_000_000);
+    assert_eq!(
+        payload["critical_chain"][1]["activation_usec"],
+        serde_json::Value::Null
+    );
+}
+
+#[tokio::test]
+async fn mcp_tools_call_get_boot_timeline_rejects_both_scope() {
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .uri("/mcp")
+                .method("POST")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::AUTHORIZATION, "Bearer token-1234567890ab")
+                .body(Body::from(
+                    r#"{"jsonrpc":"2.0","id":422,"method":"tools/call","params":{"name":"get_boot_timeline","arguments":{"scope":"both"}}}"#,
+                ))
+                .expect("request build"),
+        )
+        .await
+        .expect("request execution");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("collect body")
+        .to_bytes();
+    let body_json: serde_json::Value = serde_json::from_slice(&body).expect("valid json response");
+
+    assert_eq!(body_json["error"]["code"], -32602);
+    assert_eq!(body_json["error"]["data"]["code"], "invalid_scope");
+}
+
+#[tokio::test]
+async fn mcp_tools_call_list_boots_returns_newest_first() {
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .uri("/mcp")
+                .method("POST")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::AUTHORIZATION, "Bearer token-1234567890ab")
+                .body(Body::from(
+                    r#"{"jsonrpc":"2.0","id":423,"method":"tools/call","params":{"name":"list_boots","arguments":{"limit":2}}}"#,
+                ))
+                .expect("request build"),
+        )
+        .await
+        .expect("request execution");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("collect body")
+        .to_bytes();
+    let body_json: serde_json::Value = serde_json::from_slice(&body).expect("valid json response");
+
+    let payload = &body_json["result"]["structuredContent"];
+    assert_eq!(payload["total"], 3);
+    assert_eq!(payload["returned"], 2);
+    assert_eq!(payload["truncated"], true);
+    let boots = payload["boots"].as_array().expect("boots array");
+    assert_eq!(boots[0]["boot_id"], "3".repeat(32));
+    assert_eq!(boots[0]["offset"], 0);
+    assert_eq!(boots[0]["current"], true);
+    assert_eq!(boots[1]["offset"], -1);
+    assert_eq!(boots[1]["first_utc"], "2026-02-25T06:00:00.000Z");
+}
+
+#[tokio::test]
+async fn mcp_tools_call_list_sessions_returns_sessions_users_and_seats() {
+    let body_json = call_tool(
+        app(),
+        r#"{"jsonrpc":"2.0","id":440,"method":"tools/call","params":{"name":"list_sessions","arguments":{}}}"#,
+    )
+    .await;
+
+    let payload = &body_json["result"]["structuredContent"];
+    assert_eq!(payload["total"], 2);
+    assert_eq!(payload["returned"], 2);
+    assert_eq!(payload["truncated"], false);
+    let sessions = payload["sessions"].as_array().expect("sessions array");
+    assert_eq!(sessions[0]["session_id"], "2");
+    assert_eq!(sessions[0]["seat"], "seat0");
+    assert_eq!(sessions[0]["type"], "wayland");
+    assert_eq!(sessions[1]["remote"], true);
+    assert_eq!(sessions[1]["remote_host"], "192.0.2.10");
+    assert_eq!(sessions[1]["idle_since_utc"], "2026-02-27T00:30:00.000Z");
+    let users = payload["users"].as_array().expect("users array");
+    assert_eq!(users.len(), 3);
+    assert_eq!(users[2]["user"], "backup");
+    assert_eq!(users[2]["linger"], true);
+    assert_eq!(payload["seats"][0]["seat_id"], "seat0");
+    assert_eq!(payload["seats"][0]["session_ids"], serde_json::json!(["2"]));
+}
+
+#[tokio::test]
+async fn mcp_tools_call_list_sessions_filters_by_user() {
+    let body_json = call_tool(
+        app(),
+        r#"{"jsonrpc":"2.0","id":441,"method":"tools/call","params":{"name":"list_sessions","arguments":{"user":"bob"}}}"#,
+    )
+    .await;
+
+    let payload = &body_json["result"]["structuredContent"];
+    assert_eq!(payload["total"], 1);
+    assert_eq!(payload["sessions"][0]["user"], "bob");
+    let users = payload["users"].as_array().expect("users array");
+    assert_eq!(users.len(), 1);
+    assert_eq!(users[0]["uid"], 1001);
+}
+
+#[tokio::test]
+async fn mcp_tools_call_list_sessions_rejects_invalid_arguments() {
+    let body_json = call_tool(
+        app(),
+        r#"{"jsonrpc":"2.0","id":442,"method":"tools/call","params":{"name":"list_sessions","arguments":{"user":"bad name;"}}}"#,
+    )
+    .await;
+    assert_eq!(body_json["error"]["code"], -32602);
+    assert_eq!(body_json["error"]["data"]["code"], "invalid_user");
+
+    let body_json = call_tool(
+        app(),
+        r#"{"jsonrpc":"2.0","id":443,"method":"tools/call","params":{"name":"list_sessions","arguments":{"limit":0}}}"#,
+    )
+    .await;
+    assert_eq!(body_json["error"]["code"], -32602);
+    assert_eq!(body_json["error"]["data"]["code"], "invalid_limit");
+}
+
+#[tokio::test]
+async fn mcp_tools_call_list_sessions_reports_unavailable_provider() {
+    let body_json = call_tool(
+        app_with_provider(Arc::new(DegradedProvider)),
+        r#"{"jsonrpc":"2.0","id":444,"method":"tools/call","params":{"name":"list_sessions","arguments":{}}}"#,
+    )
+    .await;
+    assert_eq!(
+        body_json["error"]["data"]["code"],
+        "session_listing_unavailable"
+    );
+}
+
+#[tokio::test]
+async fn mcp_tools_call_list_machines_returns_machines_with_units() {
+    let body_json = call_tool(
+        app(),
+        r#"{"jsonrpc":"2.0","id":445,"method":"tools/call","params":{"name":"list_machines","arguments":{"limit":1}}}"#,
+    )
+    .await;
+
+    let payload = &body_json["result"]["structuredContent"];
+    assert_eq!(payload["total"], 2);
+    assert_eq!(payload["returned"], 1);
+    assert_eq!(payload["truncated"], true);
+    let machine = &payload["machines"][0];
+    assert_eq!(machine["name"], "db");
+    assert_eq!(machine["class"], "vm");
+    assert_eq!(machine["leader_pid"], 3100);
+    assert_eq!(machine["root_directory"], serde_json::Value::Null);
+    assert_eq!(machine["unit"], "machine-qemu\\x2d1\\x2ddb.scope");
+}
+
+#[tokio::test]
+async fn mcp_tools_call_list_machines_looks_up_one_machine() {
+    let body_json = call_tool(
+        app(),
+        r#"{"jsonrpc":"2.0","id":446,"method":"tools/call","params":{"name":"list_machines","arguments":{"name":"web-1"}}}"#,
+    )
+    .await;
+    let machines = body_json["result"]["structuredContent"]["machines"]
+        .as_array()
+        .expect("machines array");
+    assert_eq!(machines.len(), 1);
+    assert_eq!(machines[0]["network_interfaces"], serde_json::json!([7]));
+    assert_eq!(machines[0]["unit"], "systemd-nspawn@web-1.service");
+
+    let body_json = call_tool(
+        app(),
+        r#"{"jsonrpc":"2.0","id":447,"method":"tools/call","params":{"name":"list_machines","arguments":{"name":"missing"}}}"#,
+    )
+    .await;
+    assert_eq!(body_json["error"]["data"]["code"], "machine_not_found");
+
+    let body_json = call_tool(
+        app(),
+        r#"{"jsonrpc":"2.0","id":448,"method":"tools/call","params":{"name":"list_machines","arguments":{"name":"../web"}}}"#,
+    )
+    .await;
+    assert_eq!(body_json["error"]["code"], -32602);
+    assert_eq!(body_json["error"]["data"]["code"], "invalid_machine");
+}
+
+#[tokio::test]
+async fn mcp_tools_call_list_machines_reports_unavailable_provider() {
+    let body_json = call_tool(
+        app_with_provider(Arc::new(DegradedProvider)),
+        r#"{"jsonrpc":"2.0","id":449,"method":"tools/call","params":{"name":"list_machines","arguments":{}}}"#,
+    )
+    .await;
+    assert_eq!(
+        body_json["error"]["data"]["code"],
+        "machine_listing_unavailable"
+    );
+}
+
+#[tokio::test]
+async fn mcp_tools_call_list_jobs_returns_queue_with_ordering() {
+    let body_json = call_tool(
+        app(),
+        r#"{"jsonrpc":"2.0","id":455,"method":"tools/call","params":{"name":"list_jobs","arguments":{}}}"#,
+    )
+    .await;
+
+    let payload = &body_json["result"]["structuredContent"];
+    assert_eq!(payload["scope"], "system");
+    assert_eq!(payload["total"], 2);
+    let job = &payload["jobs"][0];
+    assert_eq!(job["job_id"], 812);
+    assert_eq!(job["unit"], "a.service");
+    assert_eq!(job["job_type"], "start");
+    assert_eq!(job["state"], "waiting");
+    assert_eq!(job["observed_for_ms"], 95_000);
+    assert_eq!(
+        job["waiting_on"],
+        serde_json::json!([{"job_id": 811, "unit": "network-online.target", "job_type": "start"}])
+    );
+    assert_eq!(payload["jobs"][1]["state"], "running");
+}
+
+#[tokio::test]
+async fn mcp_tools_call_list_jobs_validates_scope_and_limit() {
+    let body_json = call_tool(
+        app(),
+        r#"{"jsonrpc":"2.0","id":456,"method":"tools/call","params":{"name":"list_jobs","arguments":{"scope":"machine:db"}}}"#,
+    )
+    .await;
+    assert_eq!(body_json["error"]["data"]["code"], "machine_not_allowed");
+
+    let body_json = call_tool(
+        app(),
+        r#"{"jsonrpc":"2.0","id":457,"method":"tools/call","params":{"name":"list_jobs","arguments":{"limit":0}}}"#,
+    )
+    .await;
+    assert_eq!(body_json["error"]["code"], -32602);
+    assert_eq!(body_json["error"]["data"]["code"], "invalid_limit");
+}
+
+#[tokio::test]
+async fn mcp_tools_call_list_jobs_reports_unavailable_provider() {
+    let body_json = call_tool(
+        app_with_provider(Arc::new(DegradedProvider)),
+        r#"{"jsonrpc":"2.0","id":458,"method":"tools/call","params":{"name":"list_jobs","arguments":{}}}"#,
+    )
+    .await;
+    assert_eq!(
+        body_json["error"]["data"]["code"],
+        "job_listing_unavailable"
+    );
+}
+
+#[tokio::test]
+async fn mcp_tools_call_list_services_reports_pending_jobs() {
+    let body_json = call_tool(
+        app(),
+        r#"{"jsonrpc":"2.0","id":459,"method":"tools/call","params":{"name":"list_services","arguments":{}}}"#,
+    )
+    .await;
+
+    let services = body_json["result"]["structuredContent"]["services"]
+        .as_array()
+        .expect("services array");
+    let pending = |unit: &str| {
+        services
+            .iter()
+            .find(|service| service["unit"] == unit)
+            .map(|service| service["pending_job"].clone())
+            .expect("service row")
+    };
+    assert_eq!(
+        pending("a.service"),
+        serde_json::json!({"job_id": 812, "job_type": "start"})
+    );
+    assert_eq!(pending("z.service"), serde_json::Value::Null);
+}
+
+#[tokio::test]
+async fn mcp_tools_call_explain_timer_predicts_upcoming_runs() {
+    let body_json = call_tool(
+        app(),
+        r#"{"jsonrpc":"2.0","id":460,"method":"tools/call","params":{"name":"explain_timer","arguments":{"unit":"backup.timer","count":3,"time_zone":"UTC"}}}"#,
+    )
+    .await;
+
+    let payload = &body_json["result"]["structuredContent"];
+    assert_eq!(payload["unit"], "backup.timer");
+    assert_eq!(payload["time_zone"], "UTC");
+    assert_eq!(payload["calendar"][0]["parsed"], true);
+    assert_eq!(
+        payload["calendar"][0]["parse_error"],
+        serde_json::Value::Null
+    );
+    assert_eq!(payload["accuracy_usec"], 60_000_000);
+    assert_eq!(payload["randomized_delay_usec"], 1_800_000_000);
+    assert_eq!(
+        payload["upcoming"],
+        serde_json::json!([
+            {"utc": "2099-01-01T00:00:00.000Z", "local": "2099-01-01T00:00:00+00:00", "expression": "2099-*-01 00:00:00 UTC"},
+            {"utc": "2099-02-01T00:00:00.000Z", "local": "2099-02-01T00:00:00+00:00", "expression": "2099-*-01 00:00:00 UTC"},
+            {"utc": "2099-03-01T00:00:00.000Z", "local": "2099-03-01T00:00:00+00:00", "expression": "2099-*-01 00:00:00 UTC"},
+        ])
+    );
+    assert_eq!(payload["missed_runs"]["count"], 0);
+    assert_eq!(payload["overdue"], false);
+}
+
+#[tokio::test]
+async fn mcp_tools_call_explain_timer_reports_missed_runs() {
+    let body_json = call_tool(
+        app(),
+        r#"{"jsonrpc":"2.0","id":461,"method":"tools/call","params":{"name":"explain_timer","arguments":{"unit":"overdue.timer"}}}"#,
+    )
+    .await;
+
+    let payload = &body_json["result"]["structuredContent"];
+    assert_eq!(payload["time_zone"], "UTC");
+    assert_eq!(payload["upcoming"].as_array().map(Vec::len), Some(5));
+    assert_eq!(payload["missed_runs"]["count"], 1000);
+    assert_eq!(payload["missed_runs"]["capped"], true);
+    assert_eq!(
+        payload["missed_runs"]["first_missed_utc"],
+        "2020-01-01T00:00:00.000Z"
+    );
+    assert_eq!(payload["overdue"], true);
+    assert_eq!(payload["overdue_reason"], "past_due_beyond_grace");
+}
+
+#[tokio::test]
+async fn mcp_tools_call_explain_timer_validates_arguments() {
+    for (id, arguments, code) in [
+        (462, r#"{"unit":"a.service"}"#, "invalid_unit"),
+        (463, r#"{"unit":"missing.timer"}"#, "unit_not_found"),
+        (
+            464,
+            r#"{"unit":"backup.timer","count":51}"#,
+            "invalid_count",
+        ),
+        (
+            465,
+            r#"{"unit":"backup.timer","time_zone":"../etc/passwd"}"#,
+            "invalid_time_zone",
+        ),
+        (
+            466,
+            r#"{"unit":"backup.timer","time_zone":"Mars/Olympus_Mons"}"#,
+            "invalid_time_zone",
+        ),
+        (
+            467,
+            r#"{"unit":"backup.timer","scope":"both"}"#,
+            "invalid_scope",
+        ),
+    ] {
+        let body = format!(
+            r#"{{"jsonrpc":"2.0","id":{id},"method":"tools/call","params":{{"name":"explain_timer","arguments":{arguments}}}}}"#
+        );
+        let body_json = call_tool(app(), &body).await;
+        assert_eq!(body_json["error"]["code"], -32602, "{arguments}");
+        assert_eq!(body_json["error"]["data"]["code"], code, "{arguments}");
+    }
+}
+
+#[tokio::test]
+async fn mcp_tools_call_list_timers_includes_schedule_fields() {
+    let body_json = call_tool(
+        app(),
+        r#"{"jsonrpc":"2.0","id":468,"method":"tools/call","params":{"name":"list_timers","arguments":{"name_contains":"backup"}}}"#,
+    )
+    .await;
+
+    let timer = &body_json["result"]["structuredContent"]["timers"][0];
+    assert_eq!(timer["calendar"][0]["expression"], "2099-*-01 00:00:00 UTC");
+    assert_eq!(timer["monotonic"], serde_json::json!([]));
+    assert_eq!(timer["accuracy_usec"], 60_000_000);
+    assert_eq!(timer["randomized_delay_usec"], 1_800_000_000);
+}
+
+#[tokio::test]
+async fn mcp_tools_call_get_timer_history_summarizes_trigger_unit_runs() {
+    let body_json = call_tool(
+        app(),
+        r#"{"jsonrpc":"2.0","id":469,"method":"tools/call","params":{"name":"get_timer_history","arguments":{"unit":"backup.timer","end_utc":"2026-02-28T00:00:00Z","limit":2}}}"#,
+    )
+    .await;
+
+    let payload = &body_json["result"]["structuredContent"];
+    assert_eq!(payload["trigger_unit"], "backup.service");
+    assert_eq!(payload["start_utc"], "2026-02-21T00:00:00.000Z");
+    assert_eq!(payload["end_utc"], "2026-02-28T00:00:00.000Z");
+    let summary = &payload["summary"];
+    assert_eq!(summary["total"], 3);
+    assert_eq!(summary["succeeded"], 2);
+    assert_eq!(summary["failed"], 1);
+    assert_eq!(summary["success_ratio"], 0.667);
+    assert_eq!(summary["avg_duration_ms"], 450_000);
+    assert_eq!(summary["p95_duration_ms"], 720_000);
+    assert_eq!(summary["last_failure"]["result"], "exit-code");
+    assert_eq!(summary["last_failure"]["exit_status"], "2");
+    assert_eq!(payload["returned"], 2);
+    assert_eq!(payload["truncated"], true);
+    assert_eq!(payload["history_truncated"], true);
+    assert_eq!(
+        payload["runs"][0]["started_utc"],
+        "2026-02-27T02:00:00.000Z"
+    );
+    assert_eq!(payload["runs"][1]["outcome"], "failed");
+}
+
+#[tokio::test]
+async fn mcp_tools_call_get_timer_history_validates_arguments() {
+    for (id, arguments, code) in [
+        (470, r#"{"unit":"backup.service"}"#, "invalid_unit"),
+        (471, r#"{"unit":"missing.timer"}"#, "unit_not_found"),
+        (
+            472,
+            r#"{"unit":"backup.timer","limit":201}"#,
+            "invalid_limit",
+        ),
+        (
+            473,
+            r#"{"unit":"backup.timer","start_utc":"2026-01-01T00:00:00Z","end_utc":"2026-02-28T00:00:00Z"}"#,
+            "time_range_too_large",
+        ),
+        (
+            474,
+            r#"{"unit":"backup.timer","end_utc":"2026-02-28T00:00:00+01:00"}"#,
+            "invalid_utc_time",
+        ),
+        (
+            475,
+            r#"{"unit":"backup.timer","scope":"users:all"}"#,
+            "invalid_scope",
+        ),
+    ] {
+        let body = format!(
+            r#"{{"jsonrpc":"2.0","id":{id},"method":"tools/call","params":{{"name":"get_timer_history","arguments":{arguments}}}}}"#
+        );
+        let body_json = call_tool(app(), &body).await;
+        assert_eq!(body_json["error"]["code"], -32602, "{arguments}");
+        assert_eq!(body_json["error"]["data"]["code"], code, "{arguments}");
+    }
+}
+
+#[tokio::test]
+async fn mcp_tools_call_get_timer_history_without_trigger_unit_returns_no_runs() {
+    let body_json = call_tool(
+        app_with_provider(Arc::new(ScopeCollisionProvider)),
+        r#"{"jsonrpc":"2.0","id":476,"method":"tools/call","params":{"name":"get_timer_history","arguments":{"unit":"shared.timer"}}}"#,
+    )
+    .await;
+
+    let payload = &body_json["result"]["structuredContent"];
+    assert_eq!(payload["trigger_unit"], serde_json::Value::Null);
+    assert_eq!(payload["summary"]["total"], 0);
+    assert_eq!(payload["summary"]["success_ratio"], serde_json::Value::Null);
+    assert_eq!(payload["runs"], serde_json::json!([]));
+    assert_eq!(payload["history_truncated"], false);
+}
+
+#[tokio::test]
+async fn mcp_tools_call_list_services_reports_decoded_exit_and_oom_fields() {
+    let body_json = call_tool(
+        app(),
+        r#"{"jsonrpc":"2.0","id":477,"method":"tools/call","params":{"name":"list_services","arguments":{"name_contains":"b.service"}}}"#,
+    )
+    .await;
+
+    let service = &body_json["result"]["structuredContent"]["services"][0];
+    assert_eq!(service["unit"], "b.service");
+    assert_eq!(service["exec_main_status"], 1);
+    assert_eq!(service["exit_code"], "exited");
+    assert_eq!(service["exit_signal"], serde_json::Value::Null);
+    assert_eq!(service["oom_killed"], false);
+    assert_eq!(service["oom_kill_source"], serde_json::Value::Null);
+    assert_eq!(
+        service["timestamps"]["main_process_start"],
+        "2026-02-27T23:59:58.000Z"
+    );
+    assert_eq!(
+        service["timestamps"]["main_process_exit"],
+        "2026-02-28T00:00:00.000Z"
+    );
+}
+
+#[tokio::test]
+async fn mcp_tools_call_list_coredumps_returns_crash_metadata() {
+    let body_json = call_tool(
+        app(),
+        r#"{"jsonrpc":"2.0","id":478,"method":"tools/call","params":{"name":"list_coredumps","arguments":{"start_utc":"2026-02-24T00:00:00Z","end_utc":"2026-02-28T00:00:00Z","limit":2}}}"#,
+    )
+    .await;
+
+    let payload = &body_json["result"]["structuredContent"];
+    assert_eq!(payload["scope"], "system");
+    assert_eq!(payload["returned"], 2);
+    assert_eq!(payload["truncated"], true);
+    let newest = &payload["coredumps"][0];
+    assert_eq!(newest["timestamp_utc"], "2026-02-27T12:00:00.000Z");
+    assert_eq!(newest["executable"], "/usr/bin/crashy");
+    assert_eq!(newest["signal"], "SIGSEGV");
+    assert_eq!(newest["unit"], "a.service");
+    assert_eq!(newest["core_stored"], true);
+    assert_eq!(newest["stack_trace"].as_array().map(Vec::len), Some(1));
+    assert!(newest.get("core").is_none());
+    assert!(newest.get("cmdline").is_none());
+    assert!(newest.get("environ").is_none());
+}
+
+#[tokio::test]
+async fn mcp_tools_call_list_coredumps_filters_by_unit_executable_and_order() {
+    let body_json = call_tool(
+        app(),
+        r#"{"jsonrpc":"2.0","id":479,"method":"tools/call","params":{"name":"list_coredumps","arguments":{"start_utc":"2026-02-24T00:00:00Z","end_utc":"2026-02-28T00:00:00Z","unit":"a.service","executable":"/usr/bin/crashy","order":"asc"}}}"#,
+    )
+    .await;
+
+    let payload = &body_json["result"]["structuredContent"];
+    assert_eq!(payload["returned"], 2);
+    assert_eq!(payload["truncated"], false);
+    assert_eq!(
+        payload["coredumps"][0]["timestamp_utc"],
+        "2026-02-25T12:00:00.000Z"
+    );
+    assert_eq!(payload["coredumps"][1]["unit"], "a.service");
+}
+
+#[tokio::test]
+async fn mcp_tools_call_list_coredumps_validates_arguments() {
+    for (id, arguments, code) in [
+        (480, r#"{}"#, "missing_time_range"),
+        (
+            481,
+            r#"{"start_utc":"2026-02-01T00:00:00Z","end_utc":"2026-02-28T00:00:00Z"}"#,
+            "time_range_too_large",
+        ),
+        (
+            482,
+            r#"{"start_utc":"2026-02-24T00:00:00Z","end_utc":"2026-02-28T00:00:00Z","executable":"crashy"}"#,
+            "invalid_executable",
+        ),
+        (
+            483,
+            r#"{"start_utc":"2026-02-24T00:00:00Z","end_utc":"2026-02-28T00:00:00Z","scope":"both"}"#,
+            "invalid_scope",
+        ),
+        (
+            484,
+            r#"{"start_utc":"2026-02-24T00:00:00Z","end_utc":"2026-02-28T00:00:00Z","limit":0}"#,
+            "invalid_limit",
+        ),
+    ] {
+        let body = format!(
+            r#"{{"jsonrpc":"2.0","id":{id},"method":"tools/call","params":{{"name":"list_coredumps","arguments":{arguments}}}}}"#
+        );
+        let body_json = call_tool(app(), &body).await;
+        assert_eq!(body_json["error"]["code"], -32602, "{arguments}");
+        assert_eq!(body_json["error"]["data"]["code"], code, "{arguments}");
+    }
+}
+
+#[tokio::test]
+async fn mcp_tools_call_list_coredumps_reports_unavailable_provider() {
+    let body_json = call_tool(
+        app_with_provider(Arc::new(DegradedProvider)),
+        r#"{"jsonrpc":"2.0","id":485,"method":"tools/call","params":{"name":"list_coredumps","arguments":{"start_utc":"2026-02-24T00:00:00Z","end_utc":"2026-02-28T00:00:00Z"}}}"#,
+    )
+    .await;
+
+    assert_eq!(
+        body_json["error"]["data"]["code"],
+        "coredump_listing_unavailable"
+    );
+}
+
+#[tokio::test]
+async fn mcp_tools_call_diagnose_unit_bundles_failed_service_triage() {
+    let body_json = call_tool(
+        app(),
+        r#"{"jsonrpc":"2.0","id":486,"method":"tools/call","params":{"name":"diagnose_unit","arguments":{"unit":"b.service"}}}"#,
+    )
+    .await;
+
+    let payload = &body_json["result"]["structuredContent"];
+    assert_eq!(payload["unit"], "b.service");
+    assert_eq!(payload["scope"], "system");
+    assert_eq!(payload["status"]["active_state"], "failed");
+    assert!(payload["status"].get("failed_dependencies").is_none());
+    assert_eq!(payload["exit"]["result"], "exit-code");
+    assert_eq!(payload["exit"]["exit_code"], "exited");
+    assert_eq!(payload["exit"]["exec_main_status"], 1);
+    assert_eq!(
+        payload["exit"]["main_process_start_utc"],
+        "2026-02-27T23:59:58.000Z"
+    );
+    assert_eq!(payload["exit"]["oom_killed"], false);
+    assert_eq!(payload["restarts"]["flapping"], false);
+    assert_eq!(payload["failed_dependencies"], serde_json::json!([]));
+    assert_eq!(payload["recent_transitions"], serde_json::json!([]));
+    // The fixture start is more than 7 days old, so the window is clamped.
+    assert_eq!(payload["error_logs"]["since"], "last_7d");
+    assert_eq!(payload["error_logs"]["entries"], serde_json::json!([]));
+    assert_eq!(payload["triggered_by"], serde_json::Value::Null);
+    assert_eq!(payload["likely_causes"], serde_json::json!([]));
+    assert_eq!(payload["unavailable"], serde_json::json!([]));
+}
+
+#[tokio::test]
+async fn mcp_tools_call_diagnose_unit_reports_restart_counters() {
+    let body_json = call_tool(
+        app(),
+        r#"{"jsonrpc":"2.0","id":487,"method":"tools/call","params":{"name":"diagnose_unit","arguments":{"unit":"z.service","log_limit":5}}}"#,
+    )
+    .await;
+
+    let payload = &body_json["result"]["structuredContent"];
+    assert_eq!(payload["restarts"]["restart_count"], 12);
+    assert_eq!(payload["restarts"]["start_limit"]["burst"], 5);
+    assert_eq!(payload["restarts"]["flapping"], true);
+    assert_eq!(payload["error_logs"]["since"], "last_24h");
+}
+
+#[tokio::test]
+async fn mcp_tools_call_diagnose_unit_validates_arguments() {
+    for (id, arguments, code) in [
+        (488, r#"{"unit":"missing.service"}"#, "unit_not_found"),
+        (489, r#"{"unit":"b"}"#, "invalid_unit"),
+        (
+            490,
+            r#"{"unit":"b.service","scope":"both"}"#,
+            "invalid_scope",
+        ),
+        (
+            491,
+            r#"{"unit":"b.service","log_limit":0}"#,
+            "invalid_log_limit",
+        ),
+        (
+            492,
+            r#"{"unit":"b.service","transition_limit":101}"#,
+            "invalid_transition_limit",
+        ),
+    ] {
+        let body = format!(
+            r#"{{"jsonrpc":"2.0","id":{id},"method":"tools/call","params":{{"name":"diagnose_unit","arguments":{arguments}}}}}"#
+        );
+        let body_json = call_tool(app(), &body).await;
+        assert_eq!(body_json["error"]["code"], -32602, "{arguments}");
+        assert_eq!(body_json["error"]["data"]["code"], code, "{arguments}");
+    }
+}
+
+#[tokio::test]
+async fn mcp_tools_call_get_host_overview_reports_manager_and_host_facts() {
+    let body_json = call_tool(
+        app(),
+        r#"{"jsonrpc":"2.0","id":493,"method":"tools/call","params":{"name":"get_host_overview","arguments":{}}}"#,
+    )
+    .await;
+
+    let payload = &body_json["result"]["structuredContent"];
+    assert_eq!(payload["manager"]["version"], "257.4");
+    assert_eq!(payload["manager"]["system_state"], "degraded");
+    assert_eq!(payload["manager"]["virtualization"], "kvm");
+    assert_eq!(payload["manager"]["failed_units"], 1);
+    assert_eq!(payload["host"]["chassis"], "vm");
+    assert_eq!(
+        payload["host"]["operating_system"],
+        "Debian GNU/Linux 13 (trixie)"
+    );
+    assert_eq!(payload["time"]["timezone"], "Etc/UTC");
+    assert_eq!(payload["time"]["ntp_synchronized"], true);
+    assert_eq!(payload["uptime_seconds"], 86_400);
+    assert_eq!(payload["load_average"]["one_minute"], 0.5);
+    assert_eq!(payload["memory"]["memory_total_bytes"], 8_589_934_592_u64);
+}
+
+#[tokio::test]
+async fn mcp_tools_call_get_host_overview_reports_unavailable_provider() {
+    let body_json = call_tool(
+        app_with_provider(Arc::new(DegradedProvider)),
+        r#"{"jsonrpc":"2.0","id":494,"method":"tools/call","params":{"name":"get_host_overview"}}"#,
+    )
+    .await;
+
+    assert_eq!(
+        body_json["error"]["data"]["code"],
+        "host_overview_unavailable"
+    );
+}
+
+#[tokio::test]
+async fn mcp_tools_call_get_storage_status_flags_mounts_and_reports_journal_usage() {
+    let body_json = call_tool(
+        app(),
+        r#"{"jsonrpc":"2.0","id":495,"method":"tools/call","params":{"name":"get_storage_status","arguments":{}}}"#,
+    )
+    .await;
+
+    let payload = &body_json["result"]["structuredContent"];
+    assert_eq!(payload["returned"], 4);
+    assert_eq!(
+        payload["over_threshold"],
+        serde_json::json!(["/data", "/var"])
+    );
+    let mounts = &payload["mounts"];
+    assert_eq!(mounts[0]["mount_point"], "/");
+    assert_eq!(mounts[0]["mount_unit"], serde_json::Value::Null);
+    assert_eq!(mounts[0]["bytes_used_percent"], 40.0);
+    assert_eq!(mounts[1]["mount_point"], "/data");
+    assert_eq!(mounts[1]["mount_unit"], "data.mount");
+    assert_eq!(mounts[1]["inodes_used_percent"], 96.0);
+    assert_eq!(mounts[1]["over_bytes_threshold"], false);
+    assert_eq!(mounts[1]["over_inodes_threshold"], true);
+    assert_eq!(mounts[1]["stat_timed_out"], false);
+    assert_eq!(mounts[2]["mount_point"], "/mnt/nas");
+    assert_eq!(mounts[2]["stat_timed_out"], true);
+    assert_eq!(mounts[2]["bytes_used_percent"], serde_json::Value::Null);
+    assert_eq!(mounts[2]["over_bytes_threshold"], false);
+    assert_eq!(mounts[3]["over_bytes_threshold"], true);
+    assert_eq!(payload["thresholds"]["bytes_percent"], 90);
+    assert_eq!(payload["journal"]["total_bytes"], 545_259_520);
+    assert_eq!(payload["journal"]["directories"][1]["persistent"], true);
+}
+
+#[tokio::test]
+async fn mcp_tools_call_get_storage_status_applies_thresholds_and_validates() {
+    let body_json = call_tool(
+        app(),
+        r#"{"jsonrpc":"2.0","id":496,"method":"tools/call","params":{"name":"get_storage_status","arguments":{"bytes_threshold_percent":30,"inodes_threshold_percent":100,"limit":1}}}"#,
+    )
+    .await;
+
+    let payload = &body_json["result"]["structuredContent"];
+    assert_eq!(payload["over_threshold"], serde_json::json!(["/", "/var"]));
+    assert_eq!(payload["returned"], 1);
+    assert_eq!(payload["truncated"], true);
+
+    for (id, arguments, code) in [
+        (497, r#"{"bytes_threshold_percent":0}"#, "invalid_threshold"),
+        (
+            498,
+            r#"{"inodes_threshold_percent":101}"#,
+            "invalid_threshold",
+        ),
+        (499, r#"{"limit":1001}"#, "invalid_limit"),
+    ] {
+        let body = format!(
+            r#"{{"jsonrpc":"2.0","id":{id},"method":"tools/call","params":{{"name":"get_storage_status","arguments":{arguments}}}}}"#
+        );
+        let body_json = call_tool(app(), &body).await;
+        assert_eq!(body_json["error"]["code"], -32602, "{arguments}");
+        assert_eq!(body_json["error"]["data"]["code"], code, "{arguments}");
+    }
+
+    let body_json = call_tool(
+        app_with_provider(Arc::new(DegradedProvider)),
+        r#"{"jsonrpc":"2.0","id":500,"method":"tools/call","params":{"name":"get_storage_status"}}"#,
+    )
+    .await;
+    assert_eq!(
+        body_json["error"]["data"]["code"],
+        "storage_status_unavailable"
+    );
+}
+
+#[tokio::test]
+async fn mcp_tools_call_list_logs_with_previous_boot_uses_boot_window() {
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .uri("/mcp")
+                .method("POST")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::AUTHORIZATION, "Bearer token-1234567890ab")
+                .body(Body::from(
+                    r#"{"jsonrpc":"2.0","id":424,"method":"tools/call","params":{"name":"list_logs","arguments":{"boot":-1}}}"#,
+                ))
+                .expect("request build"),
+        )
+        .await
+        .expect("request execution");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("collect body")
+        .to_bytes();
+    let body_json: serde_json::Value = serde_json::from_slice(&body).expect("valid json response");
+
+    let window = &body_json["result"]["structuredContent"]["window"];
+    assert_eq!(window["boot_id"], "2".repeat(32));
+    assert_eq!(window["start_utc"], "2026-02-25T06:00:00.000Z");
+    assert_eq!(window["end_utc"], "2026-02-26T22:10:00.251Z");
+}
+
+#[tokio::test]
+async fn mcp_tools_call_list_logs_rejects_boot_with_explicit_window() {
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .uri("/mcp")
+                .method("POST")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::AUTHORIZATION, "Bearer token-1234567890ab")
+                .body(Body::from(
+                    r#"{"jsonrpc":"2.0","id":425,"method":"tools/call","params":{"name":"list_logs","arguments":{"boot":"current","end_utc":"2026-02-27T01:00:00Z"}}}"#,
+                ))
+                .expect("request build"),
+        )
+        .await
+        .expect("request execution");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("collect body")
+        .to_bytes();
+    let body_json: serde_json::Value = serde_json::from_slice(&body).expect("valid json response");
+
+    assert_eq!(body_json["error"]["code"], -32602);
+    assert_eq!(body_json["error"]["data"]["code"], "invalid_time_range");
+}
+
+#[tokio::test]
+async fn mcp_tools_call_list_logs_rejects_unknown_boot() {
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .uri("/mcp")
+                .method("POST")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::AUTHORIZATION, "Bearer token-1234567890ab")
+                .body(Body::from(
+                    r#"{"jsonrpc":"2.0","id":426,"method":"tools/call","params":{"name":"list_logs","arguments":{"boot":"-7"}}}"#,
+                ))
+                .expect("request build"),
+        )
+        .await
+        .expect("request execution");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("collect body")
+        .to_bytes();
+    let body_json: serde_json::Value = serde_json::from_slice(&body).expect("valid json response");
+
+    assert_eq!(body_json["error"]["code"], -32602);
+    assert_eq!(body_json["error"]["data"]["code"], "boot_not_found");
+}
+
+#[tokio::test]
+async fn mcp_tools_call_list_services_filters_flapping_services() {
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .uri("/mcp")
+                .method("POST")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::AUTHORIZATION, "Bearer token-1234567890ab")
+                .body(Body::from(
+                    r#"{"jsonrpc":"2.0","id":427,"method":"tools/call","params":{"name":"list_services","arguments":{"state":"flapping"}}}"#,
+                ))
+                .expect("request build"),
+        )
+        .await
+        .expect("request execution");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("collect body")
+        .to_bytes();
+    let body_json: serde_json::Value = serde_json::from_slice(&body).expect("valid json response");
+
+    let payload = &body_json["result"]["structuredContent"];
+    assert_eq!(payload["total"], 1);
+    let service = &payload["services"][0];
+    assert_eq!(service["unit"], "z.service");
+    assert_eq!(service["active_state"], "active");
+    assert_eq!(service["flapping"], true);
+    assert_eq!(service["restart_count"], 12);
+    assert_eq!(service["start_limit"]["burst"], 5);
+    assert_eq!(service["start_limit"]["interval_usec"], 10_000_000);
+    assert_eq!(service["start_limit"]["hit"], false);
+}
+
+#[tokio::test]
+async fn mcp_tools_call_list_services_summary_counts_flapping_services() {
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .uri("/mcp")
+                .method("POST")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::AUTHORIZATION, "Bearer token-1234567890ab")
+                .body(Body::from(
+                    r#"{"jsonrpc":"2.0","id":428,"method":"tools/call","params":{"name":"list_services","arguments":{"summary":true}}}"#,
+                ))
+                .expect("request build"),
+        )
+        .await
+        .expect("request execution");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("collect body")
+        .to_bytes();
+    let body_json: serde_json::Value = serde_json::from_slice(&body).expect("valid json response");
+
+    let summary = &body_json["result"]["structuredContent"]["summary"];
+    assert_eq!(summary["flapping_count"], 1);
+    assert_eq!(summary["counts_by_active_state"]["active"], 1);
+}
+
+/// Provider whose service rows can change between calls, counting listings and refreshes.
+struct ChangingProvider {
+    services: std::sync::Mutex<Vec<UnitStatus>>,
+    service_list_calls: AtomicUsize,
+    refresh_calls: AtomicUsize,
+}
+
+impl ChangingProvider {
+    fn new() -> Self {
+        Self {
+            services: std::sync::Mutex::new(system_services()),
+            service_list_calls: AtomicUsize::new(0),
+            refresh_calls: AtomicUsize::new(0),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl UnitProvider for ChangingProvider {
+    async fn system_state(&self, _scope: UnitScope) -> Result<String, crate::errors::AppError> {
+        Ok("running".to_string())
+    }
+
+    /// Returns the current rows and counts full listings.
+    async fn list_service_units(
+        &self,
+        _scope: UnitScope,
+    ) -> Result<Vec<UnitStatus>, crate::errors::AppError> {
+        self.service_list_calls.fetch_add(1, Ordering::SeqCst);
+        Ok(self.services.lock().expect("services lock").clone())
+    }
+
+    async fn list_journal_logs(
+        &self,
+        _query: &LogQuery,
+    ) -> Result<LogQueryResult, crate::errors::AppError> {
+        Ok(LogQueryResult {
+            entries: Vec::new(),
+            total_scanned: Some(0),
+            has_more: false,
+        })
+    }
+
+    async fn list_timer_units(
+        &self,
+        _scope: UnitScope,
+    ) -> Result<Vec<TimerStatus>, crate::errors::AppError> {
+        Ok(system_timers())
+    }
+
+    /// Returns current rows of the named units without counting a full listing.
+    async fn refresh_units(
+        &self,
+        _scope: UnitScope,
+        units: &[String],
+    ) -> Result<UnitRefresh, crate::errors::AppError> {
+        self.refresh_calls.fetch_add(1, Ordering::SeqCst);
+        let services = self
+            .services
+            .lock()
+            .expect("services lock")
+            .iter()
+            .filter(|row| units.contains(&row.unit))
+            .cloned()
+            .collect();
+        Ok(UnitRefresh {
+            services,
+            timers: Vec::new(),
+        })
+    }
+}
+
+/// Sends one authenticated `tools/call` body and returns the JSON-RPC response.
+async fn call_tool(app: Router, body: &str) -> serde_json::Value {
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/mcp")
+                .method("POST")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::AUTHORIZATION, "Bearer token-1234567890ab")
+                .body(Body::from(body.to_owned()))
+                .expect("request build"),
+        )
+        .await
+        .expect("request execution");
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("collect body")
+        .to_bytes();
+    serde_json::from_slice(&body).expect("valid json response")
+}
+
+#[tokio::test]
+async fn mcp_tools_call_list_services_serves_synced_cache_with_age() {
+    let provider = Arc::new(ChangingProvider::new());
+    let cache = Arc::new(CachedUnitProvider::new(provider.clone()));
+
+    let uncached = call_tool(
+        app_with_provider(cache.clone()),
+        r#"{"jsonrpc":"2.0","id":429,"method":"tools/call","params":{"name":"list_services","arguments":{}}}"#,
+    )
+    .await;
+    assert!(uncached["result"]["structuredContent"]["cache_age_ms"].is_null());
+    assert_eq!(provider.service_list_calls.load(Ordering::SeqCst), 1);
+
+    cache.resync(UnitScope::System).await;
+    assert_eq!(provider.service_list_calls.load(Ordering::SeqCst), 2);
+    for body in [
+        r#"{"jsonrpc":"2.0","id":430,"method":"tools/call","params":{"name":"list_services","arguments":{}}}"#,
+        r#"{"jsonrpc":"2.0","id":431,"method":"tools/call","params":{"name":"list_services","arguments":{"summary":true}}}"#,
+        r#"{"jsonrpc":"2.0","id":432,"method":"tools/call","params":{"name":"list_timers","arguments":{}}}"#,
+    ] {
+        let body_json = call_tool(app_with_provider(cache.clone()), body).await;
+        let payload = &body_json["result"]["structuredContent"];
+        assert!(payload["cache_age_ms"].is_u64(), "{payload}");
+    }
+    assert_eq!(provider.service_list_calls.load(Ordering::SeqCst), 2);
+
+    let body_json = call_tool(
+        app_with_provider(cache.clone()),
+        r#"{"jsonrpc":"2.0","id":433,"method":"tools/call","params":{"name":"list_services","arguments":{}}}"#,
+    )
+    .await;
+    let payload = &body_json["result"]["structuredContent"];
+    assert_eq!(payload["total"], 3);
+    assert_eq!(payload["services"][0]["unit"], "a.service");
+}
+
+#[tokio::test]
+async fn unit_cache_applies_change_and_removal_events_and_falls_back_when_disconnected() {
+    let provider = Arc::new(ChangingProvider::new());
+    let cache = Arc::new(CachedUnitProvider::new(provider.clone()));
+    cache
+        .apply(vec![UnitEvent::Resync(UnitScope::System)])
+        .await;
+    assert_eq!(provider.service_list_calls.load(Ordering::SeqCst), 1);
+
+    provider
+        .services
+        .lock()
+        .expect("services lock")
+        .iter_mut()
+        .find(|row| row.unit == "a.service")
+        .expect("a.service")
+        .active_state = "active".to_string();
+    cache
+        .apply(vec![
+            UnitEvent::Changed {
+                scope: UnitScope::System,
+                unit: "a.service".to_string(),
+            },
+            UnitEvent::Changed {
+                scope: UnitScope::System,
+                unit: "a.service".to_string(),
+            },
+            UnitEvent::Changed {
+                scope: UnitScope::System,
+                unit: "dev-sda.device".to_string(),
+            },
+        ])
+        .await;
+    assert_eq!(provider.refresh_calls.load(Ordering::SeqCst), 1);
+    assert_eq!(provider.service_list_calls.load(Ordering::SeqCst), 1);
+
+    cache
+        .apply(vec![UnitEvent::Removed {
+            scope: UnitScope::System,
+            unit: "b.service".to_string(),
+        }])
+        .await;
+    let rows = cache
+        .list_service_units(UnitScope::System)
+        .await
+        .expect("cached rows");
+    assert_eq!(provider.service_list_calls.load(Ordering::SeqCst), 1);
+    assert_eq!(rows.len(), 2);
+    assert!(
+        rows.iter()
+            .any(|row| row.unit == "a.service" && row.active_state == "active")
+    );
+    assert!(!rows.iter().any(|row| row.unit == "b.service"));
+    assert!(cache.cache_age(UnitScope::System).is_some());
+    assert!(cache.cache_age(UnitScope::User).is_none());
+
+    cache
+        .apply(vec![UnitEvent::Disconnected(UnitScope::System)])
+        .await;
+    assert!(cache.cache_age(UnitScope::System).is_none());
+    let rows = cache
+        .list_service_units(UnitScope::System)
+        .await
+        .expect("live rows");
+    assert_eq!(provider.service_list_calls.load(Ordering::SeqCst), 2);
+    assert_eq!(rows.len(), 3);
+}
+
 struct CountingProvider {
     system_state_calls: Arc<AtomicUsize>,
     service_list_calls: Arc<AtomicUsize>,
@@ -0,0 +1,641 @@
+//! Read-only time zone rules from TZif files for calendar predictions.
+//!
+//! The manager evaluates `OnCalendar=` against the host's zoneinfo database, so
+//! predictions read the same TZif files instead of a database compiled into
+//! the binary (as `chrono-tz` does), which would drift from the host's tzdata
+//! and grow a binary built for size. Only UTC offsets are resolved: no
+//! abbreviations, leap seconds, or version 1 (32-bit only) files.
+
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, Utc};
+use std::{
+    collections::HashMap,
+    fs,
+    io::Read,
+    path::Path,
+    sync::{Mutex, OnceLock},
+};
+
+use crate::errors::AppError;
+
+const ZONEINFO_DIRECTORY: &str = "/usr/share/zoneinfo";
+const HOST_LOCALTIME_PATH: &str = "/etc/localtime";
+const MAX_TZIF_BYTES: u64 = 256 * 1024;
+const MAX_TIME_ZONE_NAME_LENGTH: usize = 64;
+
+/// Named time zone resolving UTC offsets for any instant.
+///
+/// Offsets come from the TZif transition table; instants after the last
+/// transition follow the POSIX rule in the file footer, as `localtime(3)` does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeZone {
+    name: String,
+    initial_offset: i32,
+    transitions: Vec<(i64, i32)>,
+    rule: Option<PosixRule>,
+}
+
+/// POSIX `TZ` rule: a standard offset and optional daylight saving period.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PosixRule {
+    std_offset: i32,
+    dst: Option<DstRule>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DstRule {
+    offset: i32,
+    start: (RuleDate, i32),
+    end: (RuleDate, i32),
+}
+
+/// Day of a POSIX rule transition within one year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RuleDate {
+    /// `Jn`: day 1-365, never counting February 29.
+    Julian(u16),
+    /// `n`: zero-based day of the year, counting February 29.
+    DayOfYear(u16),
+    /// `Mm.w.d`: weekday `d` (0 is Sunday) of week `w` (5 is the last) of month `m`.
+    MonthWeekDay(u32, u32, u32),
+}
+
+impl TimeZone {
+    /// Returns Coordinated Universal Time.
+    pub fn utc() -> Self {
+        Self {
+            name: "UTC".to_string(),
+            initial_offset: 0,
+            transitions: Vec::new(),
+            rule: None,
+        }
+    }
+
+    /// Loads an IANA time zone such as `Europe/Berlin` from the system zoneinfo database.
+    ///
+    /// `UTC` needs no database. Names are validated before any file access so
+    /// only regular files below the zoneinfo directory are ever read. Loaded
+    /// zones are cached for the life of the process, so each file is read once.
+    pub fn load(name: &str) -> Result<Self, AppError> {
+        static LOADED: OnceLock<Mutex<HashMap<String, TimeZone>>> = OnceLock::new();
+
+        let invalid = || {
+            AppError::bad_request(
+                "invalid_time_zone",
+                "time_zone must be UTC or an IANA time zone name such as Europe/Berlin",
+            )
+        };
+        if name.eq_ignore_ascii_case("UTC") {
+            return Ok(Self::utc());
+        }
+        if !is_valid_time_zone_name(name) {
+            return Err(invalid());
+        }
+        let loaded = LOADED.get_or_init(Mutex::default);
+        if let Some(zone) = loaded.lock().expect("time zone cache lock").get(name) {
+            return Ok(zone.clone());
+        }
+        let data = read_tzif(&Path::new(ZONEINFO_DIRECTORY).join(name)).ok_or_else(invalid)?;
+        let zone = Self::from_tzif(name, &data).ok_or_else(invalid)?;
+        loaded
+            .lock()
+            .expect("time zone cache lock")
+            .insert(name.to_string(), zone.clone());
+        Ok(zone)
+    }
+
+    /// Returns the host's local time zone from `/etc/localtime`, falling back to UTC.
+    ///
+    /// The name is taken from the zoneinfo path the link points to, or is
+    /// `localtime` when the file is not a link into the database. The file is
+    /// reread on every call because `timedatectl set-timezone` replaces it at
+    /// runtime; async callers run this on a blocking worker.
+    pub fn host_local() -> Self {
+        let Some(data) = read_tzif(Path::new(HOST_LOCALTIME_PATH)) else {
+            return Self::utc();
+        };
+        let name = fs::read_link(HOST_LOCALTIME_PATH)
+            .ok()
+            .and_then(|target| {
+                let target = target.to_string_lossy().into_owned();
+                target
+                    .split_once("zoneinfo/")
+                    .map(|(_, name)| name.to_string())
+            })
+            .filter(|name| is_valid_time_zone_name(name))
+            .unwrap_or_else(|| "localtime".to_string());
+        Self::from_tzif(&name, &data).unwrap_or_else(Self::utc)
+    }
+
+    /// Parses the 64-bit block and footer rule of TZif version 2-4 data.
+    pub(crate) fn from_tzif(name: &str, data: &[u8]) -> Option<Self> {
+        let first = TzifHeader::parse(data)?;
+        if first.version < b'2' {
+            return None;
+        }
+        let block = data.get(first.v1_block_end()..)?;
+        let header = TzifHeader::parse(block)?;
+        let time_size = 8;
+        let times_start = 44;
+        let indices_start = times_start + header.time_count * time_size;
+        let types_start = indices_start + header.time_count;
+        let types_end = types_start + header.type_count * 6;
+        if header.type_count == 0 || block.len() < types_end {
+            return None;
+        }
+        let offsets = (0..header.type_count)
+            .map(|index| {
+                let at = types_start + index * 6;
+                i32::from_be_bytes(block[at..at + 4].try_into().expect("4 bytes"))
+            })
+            .collect::<Vec<_>>();
+        let transitions = (0..header.time_count)
+            .map(|index| {
+                let at = times_start + index * time_size;
+                let time = i64::from_be_bytes(block[at..at + 8].try_into().expect("8 bytes"));
+                let offset = *offsets.get(usize::from(block[indices_start + index]))?;
+                Some((time, offset))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let footer = block.get(header.v2_block_end()..)?;
+        let footer = footer.strip_prefix(b"\n")?;
+        let end = footer.iter().position(|byte| *byte == b'\n')?;
+        let footer = std::str::from_utf8(&footer[..end]).ok()?;
+        let rule = if footer.is_empty() {
+            None
+        } else {
+            Some(PosixRule::parse(footer)?)
+        };
+        Some(Self {
+            name: name.to_string(),
+            initial_offset: offsets[0],
+            transitions,
+            rule,
+        })
+    }
+
+    /// Builds a zone from a POSIX `TZ` rule alone, such as `CET-1CEST,M3.5.0,M10.5.0/3`.
+    #[cfg(test)]
+    pub(crate) fn from_posix(name: &str, rule: &str) -> Option<Self> {
+        let rule = PosixRule::parse(rule)?;
+        Some(Self {
+            name: name.to_string(),
+            initial_offset: rule.std_offset,
+            transitions: Vec::new(),
+            rule: Some(rule),
+        })
+    }
+
+    /// Returns the zone name, such as `Europe/Berlin` or `UTC`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the UTC offset in seconds east of Greenwich at `instant` (Unix seconds).
+    pub fn offset_at(&self, instant: i64) -> i32 {
+        let index = self
+            .transitions
+            .partition_point(|(time, _)| *time <= instant);
+        if index == 0 {
+            return match (&self.rule, self.transitions.is_empty()) {
+                (Some(rule), true) => rule.offset_at(instant),
+                _ => self.initial_offset,
+            };
+        }
+        if index == self.transitions.len()
+            && let Some(rule) = &self.rule
+        {
+            return rule.offset_at(instant);
+        }
+        self.transitions[index - 1].1
+    }
+
+    /// Converts a UTC instant to local time in this zone.
+    pub fn to_local(&self, instant: DateTime<Utc>) -> DateTime<FixedOffset> {
+        let offset = FixedOffset::east_opt(self.offset_at(instant.timestamp()))
+            .unwrap_or_else(|| FixedOffset::east_opt(0).expect("zero offset"));
+        instant.with_timezone(&offset)
+    }
+
+    /// Resolves a local wall-clock time to its earliest and latest UTC instants.
+    ///
+    /// Both are equal outside transitions. Times repeated when clocks go back
+    /// yield both occurrences; times skipped when clocks go forward resolve
+    /// past the gap, like `mktime(3)` normalizing with the previous offset.
+    pub fn resolve_local(&self, local: NaiveDateTime) -> (DateTime<Utc>, DateTime<Utc>) {
+        let as_utc = local.and_utc();
+        let candidates = [
+            self.offset_at((as_utc - Duration::days(1)).timestamp()),
+            self.offset_at((as_utc + Duration::days(1)).timestamp()),
+        ]
+        .map(|offset| as_utc - Duration::seconds(i64::from(offset)));
+        let valid = candidates
+            .iter()
+            .filter(|candidate| {
+                **candidate + Duration::seconds(i64::from(self.offset_at(candidate.timestamp())))
+                    == as_utc
+            })
+            .copied()
+            .collect::<Vec<_>>();
+        match (valid.iter().min(), valid.iter().max()) {
+            (Some(earliest), Some(latest)) => (*earliest, *latest),
+            _ => (candidates[0], candidates[0]),
+        }
+    }
+}
+
+/// Accepts IANA-style names: ASCII alphanumerics, `_`, `+`, `-`, and `/`
+/// separators, without empty, `.`-leading, or parent components.
+fn is_valid_time_zone_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= MAX_TIME_ZONE_NAME_LENGTH
+        && name.split('/').all(|component| {
+            !component.is_empty()
+                && !component.starts_with(['.', '-'])
+                && component
+                    .bytes()
+                    .all(|byte| byte.is_ascii_alphanumeric() || b"_+-".contains(&byte))
+        })
+}
+
+/// Reads a bounded regular TZif file.
+fn read_tzif(path: &Path) -> Option<Vec<u8>> {
+    if !fs::metadata(path).ok()?.is_file() {
+        return None;
+    }
+    let mut data = Vec::new();
+    fs::File::open(path)
+        .ok()?
+        .take(MAX_TZIF_BYTES)
+        .read_to_end(&mut data)
+        .ok()?;
+    data.starts_with(b"TZif").then_some(data)
+}
+
+/// Counts of one TZif header (RFC 8536 section 3.1).
+struct TzifHeader {
+    version: u8,
+    ut_count: usize,
+    std_count: usize,
+    leap_count: usize,
+    time_count: usize,
+    type_count: usize,
+    char_count: usize,
+}
+
+impl TzifHeader {
+    fn parse(data: &[u8]) -> Option<Self> {
+        if data.len() < 44 || !data.starts_with(b"TZif") {
+            return None;
+        }
+        let count = |index: usize| {
+            let at = 20 + index * 4;
+            u32::from_be_bytes(data[at..at + 4].try_into().expect("4 bytes")) as usize
+        };
+        Some(Self {
+            version: data[4],
+            ut_count: count(0),
+            std_count: count(1),
+            leap_count: count(2),
+            time_count: count(3),
+            type_count: count(4),
+            char_count: count(5),
+        })
+    }
+
+    fn block_end(&self, time_size: usize) -> usize {
+        44 + self.time_count * (time_size + 1)
+            + self.type_count * 6
+            + self.char_count
+            + self.leap_count * (time_size + 4)
+            + self.std_count
+            + self.ut_count
+    }
+
+    fn v1_block_end(&self) -> usize {
+        self.block_end(4)
+    }
+
+    fn v2_block_end(&self) -> usize {
+        self.block_end(8)
+    }
+}
+
+impl PosixRule {
+    /// Parses `STDoffset[DST[offset][,start[/time],end[/time]]]`.
+    ///
+    /// A DST zone without explicit dates uses the US rules `M3.2.0,M11.1.0`.
+    fn parse(value: &str) -> Option<Self> {
+        let mut input = value.as_bytes();
+        skip_zone_abbreviation(&mut input)?;
+        let std_offset = -parse_signed_seconds(&mut input)?;
+        if input.is_empty() {
+            return Some(Self {
+                std_offset,
+                dst: None,
+            });
+        }
+        skip_zone_abbreviation(&mut input)?;
+        let offset = if input.first().is_some_and(|byte| *byte != b',') {
+            -parse_signed_seconds(&mut input)?
+        } else {
+            std_offset + 3600
+        };
+        let (start, end) = if input.is_empty() {
+            (
+                (RuleDate::MonthWeekDay(3, 2, 0), 7200),
+                (RuleDate::MonthWeekDay(11, 1, 0), 7200),
+            )
+        } else {
+            input = input.strip_prefix(b",")?;
+            let start = parse_rule_transition(&mut input)?;
+            input = input.strip_prefix(b",")?;
+            let end = parse_rule_transition(&mut input)?;
+            (start, end)
+        };
+        input.is_empty().then_some(Self {
+            std_offset,
+            dst: Some(DstRule { offset, start, end }),
+        })
+    }
+
+    /// Returns the offset in force at `instant` (Unix seconds).
+    fn offset_at(&self, instant: i64) -> i32 {
+        let Some(dst) = &self.dst else {
+            return self.std_offset;
+        };
+        let year = DateTime::from_timestamp(instant + i64::from(self.std_offset), 0)
+            .map(|local| local.year())
+            .unwrap_or(1970);
+        let start =
+            dst.start.0.local_midnight(year) + i64::from(dst.start.1) - i64::from(self.std_offset);
+        let end = dst.end.0.local_midnight(year) + i64::from(dst.end.1) - i64::from(dst.offset);
+        let in_dst = if start < end {
+            (start..end).contains(&instant)
+        } else {
+            !(end..start).contains(&instant)
+        };
+        if in_dst { dst.offset } else { self.std_offset }
+    }
+}
+
+impl RuleDate {
+    /// Returns local midnight of this rule day in `year` as Unix seconds of local time.
+    fn local_midnight(self, year: i32) -> i64 {
+        let january_first = NaiveDate::from_ymd_opt(year, 1, 1).unwrap_or_default();
+        let leap = NaiveDate::from_ymd_opt(year, 2, 29).is_some();
+        let date = match self {
+            Self::Julian(day) => {
+                let skip_leap_day = u64::from(leap && day >= 60);
+                january_first + chrono::Days::new(u64::from(day) - 1 + skip_leap_day)
+            }
+            Self::DayOfYear(day) => january_first + chrono::Days::new(u64::from(day)),
+            Self::MonthWeekDay(month, week, weekday) => {
+                let first = NaiveDate::from_ymd_opt(year, month, 1).unwrap_or(january_first);
+                let first_weekday = first.weekday().num_days_from_sunday();
+                let mut day = 1 + (weekday + 7 - first_weekday) % 7 + (week - 1) * 7;
+                while NaiveDate::from_ymd_opt(year, month, day).is_none() {
+                    day -= 7;
+                }
+                NaiveDate::from_ymd_opt(year, month, day).unwrap_or(first)
+            }
+        };
+        date.and_hms_opt(0, 0, 0)
+            .unwrap_or_default()
+            .and_utc()
+            .timestamp()
+    }
+}
+
+/// Skips an alphabetic abbreviation of 3+ letters or a quoted `<...>` one.
+fn skip_zone_abbreviation(input: &mut &[u8]) -> Option<()> {
+    let length = if input.first() == Some(&b'<') {
+        input.iter().position(|byte| *byte == b'>')? + 1
+    } else {
+        let length = input
+            .iter()
+            .take_while(|byte| byte.is_ascii_alphabetic())
+            .count();
+        if length < 3 {
+            return None;
+        }
+        length
+    };
+    *input = &input[length..];
+    Some(())
+}
+
+/// Parses `[+-]hh[:mm[:ss]]` into seconds.
+fn parse_signed_seconds(input: &mut &[u8]) -> Option<i32> {
+    let sign = match input.first() {
+        Some(b'-') => {
+            *input = &input[1..];
+            -1
+        }
+        Some(b'+') => {
+            *input = &input[1..];
+            1
+        }
+        _ => 1,
+    };
+    let mut seconds = 0;
+    for (index, scale) in [3600, 60, 1].into_iter().enumerate() {
+        if index > 0 {
+            match input.strip_prefix(b":") {
+                Some(rest) => *input = rest,
+                None => break,
+            }
+        }
+        let digits = input
+            .iter()
+            .take_while(|byte| byte.is_ascii_digit())
+            .count();
+        if digits == 0 || digits > 3 {
+            return None;
+        }
+        let value: i32 = std::str::from_utf8(&input[..digits]).ok()?.parse().ok()?;
+        *input = &input[digits..];
+        seconds += value * scale;
+    }
+    Some(sign * seconds)
+}
+
+/// Parses one `date[/time]` transition; the time defaults to 02:00.
+fn parse_rule_transition(input: &mut &[u8]) -> Option<(RuleDate, i32)> {
+    let number = |input: &mut &[u8]| -> Option<u32> {
+        let digits = input
+            .iter()
+            .take_while(|byte| byte.is_ascii_digit())
+            .count();
+        let value = std::str::from_utf8(&input[..digits]).ok()?.parse().ok()?;
+        *input = &input[digits..];
+        Some(value)
+    };
+    let date = match input.first()? {
+        b'J' => {
+            *input = &input[1..];
+            let day = number(input)?;
+            (1..=365)
+                .contains(&day)
+                .then_some(RuleDate::Julian(day as u16))?
+        }
+        b'M' => {
+            *input = &input[1..];
+            let month = number(input)?;
+            *input = input.strip_prefix(b".")?;
+            let week = number(input)?;
+            *input = input.strip_prefix(b".")?;
+            let weekday = number(input)?;
+            ((1..=12).contains(&month) && (1..=5).contains(&week) && weekday <= 6)
+                .then_some(RuleDate::MonthWeekDay(month, week, weekday))?
+        }
+        _ => {
+            let day = number(input)?;
+            (day <= 365).then_some(RuleDate::DayOfYear(day as u16))?
+        }
+    };
+    let time = match input.strip_prefix(b"/") {
+        Some(rest) => {
+            *input = rest;
+            parse_signed_seconds(input)?
+        }
+        None => 7200,
+    };
+    Some((date, time))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TimeZone, is_valid_time_zone_name};
+    use crate::errors::AppError;
+    use chrono::{DateTime, NaiveDate, Utc};
+
+    fn utc(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .expect("timestamp")
+            .with_timezone(&Utc)
+    }
+
+    fn berlin() -> TimeZone {
+        TimeZone::from_posix("Europe/Berlin", "CET-1CEST,M3.5.0,M10.5.0/3").expect("rule")
+    }
+
+    #[test]
+    fn posix_rules_switch_offsets_at_transitions() {
+        let zone = berlin();
+        assert_eq!(
+            zone.offset_at(utc("2026-03-29T00:59:59Z").timestamp()),
+            3600
+        );
+        assert_eq!(
+            zone.offset_at(utc("2026-03-29T01:00:00Z").timestamp()),
+            7200
+        );
+        assert_eq!(
+            zone.offset_at(utc("2026-10-25T00:59:59Z").timestamp()),
+            7200
+        );
+        assert_eq!(
+            zone.offset_at(utc("2026-10-25T01:00:00Z").timestamp()),
+            3600
+        );
+
+        let sydney =
+            TimeZone::from_posix("Australia/Sydney", "AEST-10AEDT,M10.1.0,M4.1.0/3").expect("rule");
+        assert_eq!(
+            sydney.offset_at(utc("2026-01-15T00:00:00Z").timestamp()),
+            39600
+        );
+        assert_eq!(
+            sydney.offset_at(utc("2026-07-15T00:00:00Z").timestamp()),
+            36000
+        );
+
+        let fixed = TimeZone::from_posix("Asia/Kolkata", "IST-5:30").expect("rule");
+        assert_eq!(
+            fixed.to_local(utc("2026-01-01T00:00:00Z")).to_rfc3339(),
+            "2026-01-01T05:30:00+05:30"
+        );
+        assert!(TimeZone::from_posix("bad", "X1").is_none());
+    }
+
+    #[test]
+    fn local_times_resolve_across_gaps_and_overlaps() {
+        let zone = berlin();
+        let local = |day: u32, month: u32, hour: u32, minute: u32| {
+            NaiveDate::from_ymd_opt(2026, month, day)
+                .and_then(|date| date.and_hms_opt(hour, minute, 0))
+                .expect("local time")
+        };
+        assert_eq!(
+            zone.resolve_local(local(1, 7, 12, 0)),
+            (utc("2026-07-01T10:00:00Z"), utc("2026-07-01T10:00:00Z"))
+        );
+        assert_eq!(
+            zone.resolve_local(local(29, 3, 2, 30)).0,
+            utc("2026-03-29T01:30:00Z")
+        );
+        assert_eq!(
+            zone.resolve_local(local(25, 10, 2, 30)),
+            (utc("2026-10-25T00:30:00Z"), utc("2026-10-25T01:30:00Z"))
+        );
+    }
+
+    #[test]
+    fn parses_tzif_transitions_and_footer() {
+        let mut block = Vec::new();
+        let header = |block: &mut Vec<u8>, version: u8, time_count: u32| {
+            block.extend_from_slice(b"TZif");
+            block.push(version);
+            block.extend_from_slice(&[0; 15]);
+            for count in [0, 0, 0, time_count, 2, 8] {
+                block.extend_from_slice(&u32::to_be_bytes(count));
+            }
+        };
+        header(&mut block, b'2', 0);
+        block.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        block.extend_from_slice(b"LMT\0CET\0");
+        header(&mut block, b'2', 1);
+        block.extend_from_slice(&i64::to_be_bytes(-2_422_051_208));
+        block.push(1);
+        block.extend_from_slice(&i32::to_be_bytes(3208));
+        block.extend_from_slice(&[0, 0]);
+        block.extend_from_slice(&i32::to_be_bytes(3600));
+        block.extend_from_slice(&[0, 4]);
+        block.extend_from_slice(b"LMT\0CET\0");
+        block.extend_from_slice(b"\nCET-1CEST,M3.5.0,M10.5.0/3\n");
+
+        let zone = TimeZone::from_tzif("Europe/Berlin", &block).expect("tzif");
+        assert_eq!(zone.name(), "Europe/Berlin");
+        assert_eq!(zone.offset_at(-2_500_000_000), 3208);
+        assert_eq!(
+            zone.offset_at(utc("2026-07-01T00:00:00Z").timestamp()),
+            7200
+        );
+        assert_eq!(
+            zone.offset_at(utc("2026-12-01T00:00:00Z").timestamp()),
+            3600
+        );
+        assert!(TimeZone::from_tzif("broken", &block[..40]).is_none());
+        block[4] = 0;
+        assert!(TimeZone::from_tzif("version 1", &block).is_none());
+    }
+
+    #[test]
+    fn validates_time_zone_names_before_file_access() {
+        assert!(is_valid_time_zone_name("Europe/Berlin"));
+        assert!(is_valid_time_zone_name("America/Port-au-Prince"));
+        assert!(is_valid_time_zone_name("Etc/GMT+5"));
+        assert!(!is_valid_time_zone_name("../etc/passwd"));
+        assert!(!is_valid_time_zone_name("/etc/localtime"));
+        assert!(!is_valid_time_zone_name("Europe//Berlin"));
+        assert!(!is_valid_time_zone_name("Europe/.hidden"));
+        assert!(matches!(
+            TimeZone::load("../../etc/shadow").expect_err("rejected"),
+            AppError::BadRequest {
+                code: "invalid_time_zone",
+                ..
+            }
+        ));
+        assert_eq!(TimeZone::load("utc").expect("utc").name(), "UTC");
+    }
+}
@@ -0,0 +1,480 @@
+//! Event-driven in-memory cache of service and timer state
+//!
+//! `CachedUnitProvider` wraps another `UnitProvider` and serves service and timer
+//! listings from per-scope snapshots. Snapshots are filled by a full resync and
+//! kept current by `UnitEvent`s decoded from systemd D-Bus signals; every other
+//! provider method is delegated to the wrapped provider unchanged.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tracing::warn;
+
+use crate::errors::AppError;
+use crate::systemd_client::{
+    BootRecord, BootTimeline, BootTiming, ConnectionHealth, CoredumpInfo, DependencyDirection,
+    HostOverview, JobInfo, LogQuery, LogQueryResult, LoginInventory, MachineInfo, ResourceSample,
+    ServiceSecuritySettings, StorageReport, TimerStatus, UnitDependencyGraph, UnitEvent, UnitInfo,
+    UnitProvider, UnitRefresh, UnitResources, UnitRunHistory, UnitScope, UnitStatus,
+    combine_scope_rows_by_key, unit_type_of, watch_unit_events,
+};
+use crate::unit_files::UnitFileView;
+
+/// Quiet period used to coalesce signal bursts into one refresh.
+const EVENT_DEBOUNCE: Duration = Duration::from_millis(250);
+/// Upper bound of events applied as one batch.
+const MAX_EVENT_BATCH: usize = 4_096;
+/// Interval of full resyncs, bounding drift of time-derived fields such as flapping.
+const FULL_RESYNC_INTERVAL: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Default)]
+struct ScopeSnapshot {
+    services: BTreeMap<String, UnitStatus>,
+    timers: BTreeMap<String, TimerStatus>,
+    /// Last time the snapshot applied bus state; `None` until the first resync.
+    refreshed_at: Option<Instant>,
+    /// Last full resync, used to schedule periodic resyncs.
+    resynced_at: Option<Instant>,
+}
+
+/// Caching `UnitProvider` decorator fed by `UnitEvent`s.
+///
+/// A scope is served from memory only after a successful resync and until its
+/// bus connection is reported lost; unsynced scopes fall through to the wrapped
+/// provider so responses never silently go stale. Per-user scopes
+/// (`user:<uid>`, `users:all`) are always read live.
+pub struct CachedUnitProvider {
+    inner: Arc<dyn UnitProvider>,
+    system: RwLock<ScopeSnapshot>,
+    user: RwLock<ScopeSnapshot>,
+}
+
+impl CachedUnitProvider {
+    /// Creates an empty cache; every read falls through until the first resync.
+    pub fn new(inner: Arc<dyn UnitProvider>) -> Self {
+        Self {
+            inner,
+            system: RwLock::new(ScopeSnapshot::default()),
+            user: RwLock::new(ScopeSnapshot::default()),
+        }
+    }
+
+    /// Creates the cache and starts the event worker plus one signal watcher per scope.
+    pub fn spawn(inner: Arc<dyn UnitProvider>) -> Arc<Self> {
+        let cache = Arc::new(Self::new(inner));
+        let (sender, receiver) = mpsc::unbounded_channel();
+        for scope in [UnitScope::System, UnitScope::User] {
+            tokio::spawn(watch_unit_events(scope, sender.clone()));
+        }
+        tokio::spawn(Arc::clone(&cache).run(receiver));
+        cache
+    }
+
+    /// Applies events until the channel closes, debouncing bursts into batches.
+    ///
+    /// Synced scopes are fully resynced every `FULL_RESYNC_INTERVAL`.
+    pub async fn run(self: Arc<Self>, mut events: mpsc::UnboundedReceiver<UnitEvent>) {
+        let mut resync_timer = tokio::time::interval(FULL_RESYNC_INTERVAL);
+        resync_timer.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                event = events.recv() => {
+                    let Some(event) = event else {
+                        return;
+                    };
+                    tokio::time::sleep(EVENT_DEBOUNCE).await;
+                    let mut batch = vec![event];
+                    while batch.len() < MAX_EVENT_BATCH {
+                        match events.try_recv() {
+                            Ok(event) => batch.push(event),
+                            Err(_) => break,
+                        }
+                    }
+                    self.apply(batch).await;
+                }
+                _ = resync_timer.tick() => {
+                    for scope in [UnitScope::System, UnitScope::User] {
+                        let due = self
+                            .snapshot(scope)
+                            .read()
+                            .expect("unit cache lock poisoned")
+                            .resynced_at
+                            .is_some_and(|at| at.elapsed() >= FULL_RESYNC_INTERVAL);
+                        if due {
+                            self.resync(scope).await;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Applies one batch of events in order.
+    ///
+    /// Removals take effect immediately; changed units are collected per scope and
+    /// refreshed once after any pending resync. Units other than services and
+    /// timers are ignored.
+    pub async fn apply(&self, events: Vec<UnitEvent>) {
+        let mut resync = BTreeSet::new();
+        let mut dirty: BTreeMap<UnitScope, BTreeSet<String>> = BTreeMap::new();
+        for event in events {
+            match event {
+                UnitEvent::Changed { scope, unit } if is_cached_unit(&unit) => {
+                    dirty.entry(scope).or_default().insert(unit);
+                }
+                UnitEvent::Removed { scope, unit } if is_cached_unit(&unit) => {
+                    if let Some(units) = dirty.get_mut(&scope) {
+                        units.remove(&unit);
+                    }
+                    let mut snapshot = self.write(scope);
+                    snapshot.services.remove(&unit);
+                    snapshot.timers.remove(&unit);
+                }
+                UnitEvent::Resync(scope) => {
+                    dirty.remove(&scope);
+                    resync.insert(scope);
+                }
+                UnitEvent::Disconnected(scope) => {
+                    dirty.remove(&scope);
+                    resync.remove(&scope);
+                    self.invalidate(scope);
+                }
+                UnitEvent::Changed { .. } | UnitEvent::Removed { .. } => {}
+            }
+        }
+
+        for scope in resync {
+            self.resync(scope).await;
+        }
+        for (scope, units) in dirty {
+            self.refresh(scope, units.into_iter().collect()).await;
+        }
+    }
+
+    /// Replaces the snapshot of `scope` with full service and timer listings.
+    ///
+    /// A failed listing invalidates the scope so reads fall through until the next
+    /// successful resync.
+    pub async fn resync(&self, scope: UnitScope) {
+        let services = self.inner.list_service_units(scope).await;
+        let timers = self.inner.list_timer_units(scope).await;
+        match (services, timers) {
+            (Ok(services), Ok(timers)) => {
+                let now = Instant::now();
+                *self.write(scope) = ScopeSnapshot {
+                    services: services
+                        .into_iter()
+                        .map(|row| (row.unit.clone(), row))
+                        .collect(),
+                    timers: timers
+                        .into_iter()
+                        .map(|row| (row.unit.clone(), row))
+                        .collect(),
+                    refreshed_at: Some(now),
+                    resynced_at: Some(now),
+                };
+            }
+            (Err(err), _) | (_, Err(err)) => {
+                warn!(scope = %scope.as_str(), error = %err, "failed to resync unit cache");
+                self.invalidate(scope);
+            }
+        }
+    }
+
+    /// Re-reads named units of a synced scope; units no longer returned are removed.
+    async fn refresh(&self, scope: UnitScope, units: Vec<String>) {
+        if units.is_empty() || self.age(scope).is_none() {
+            return;
+        }
+        match self.inner.refresh_units(scope, &units).await {
+            Ok(UnitRefresh { services, timers }) => {
+                let mut snapshot = self.write(scope);
+                if snapshot.refreshed_at.is_none() {
+                    return;
+                }
+                for unit in &units {
+                    snapshot.services.remove(unit);
+                    snapshot.timers.remove(unit);
+                }
+                snapshot
+                    .services
+                    .extend(services.into_iter().map(|row| (row.unit.clone(), row)));
+                snapshot
+                    .timers
+                    .extend(timers.into_iter().map(|row| (row.unit.clone(), row)));
+                snapshot.refreshed_at = Some(Instant::now());
+            }
+            Err(err) => {
+                warn!(scope = %scope.as_str(), error = %err, "failed to refresh cached units");
+                self.invalidate(scope);
+            }
+        }
+    }
+
+    /// Drops the snapshot of `scope` so reads fall through to the wrapped provider.
+    pub fn invalidate(&self, scope: UnitScope) {
+        *self.write(scope) = ScopeSnapshot::default();
+    }
+
+    /// Age of a synced concrete scope, or `None` when it is not served from memory.
+    fn age(&self, scope: UnitScope) -> Option<Duration> {
+        self.snapshot(scope)
+            .read()
+            .expect("unit cache lock poisoned")
+            .refreshed_at
+            .map(|at| at.elapsed())
+    }
+
+    /// Returns the lock of one cached scope; other scopes map to the system
+    /// snapshot and are never passed by callers.
+    fn snapshot(&self, scope: UnitScope) -> &RwLock<ScopeSnapshot> {
+        match scope {
+            UnitScope::User => &self.user,
+            _ => &self.system,
+        }
+    }
+
+    fn write(&self, scope: UnitScope) -> std::sync::RwLockWriteGuard<'_, ScopeSnapshot> {
+        self.snapshot(scope)
+            .write()
+            .expect("unit cache lock poisoned")
+    }
+
+    /// Reads cached rows of one concrete scope when synced.
+    fn cached_rows<T: Clone>(
+        &self,
+        scope: UnitScope,
+        select: impl Fn(&ScopeSnapshot) -> &BTreeMap<String, T>,
+    ) -> Option<Vec<T>> {
+        let snapshot = self
+            .snapshot(scope)
+            .read()
+            .expect("unit cache lock poisoned");
+        snapshot.refreshed_at?;
+        Some(select(&snapshot).values().cloned().collect())
+    }
+
+    async fn service_units_for_single_scope(
+        &self,
+        scope: UnitScope,
+    ) -> Result<Vec<UnitStatus>, AppError> {
+        match self.cached_rows(scope, |snapshot| &snapshot.services) {
+            Some(rows) => Ok(rows),
+            None => self.inner.list_service_units(scope).await,
+        }
+    }
+
+    async fn timer_units_for_single_scope(
+        &self,
+        scope: UnitScope,
+    ) -> Result<Vec<TimerStatus>, AppError> {
+        match self.cached_rows(scope, |snapshot| &snapshot.timers) {
+            Some(rows) => Ok(rows),
+            None => self.inner.list_timer_units(scope).await,
+        }
+    }
+}
+
+/// Returns true for unit types held by the cache.
+fn is_cached_unit(unit: &str) -> bool {
+    matches!(unit_type_of(unit), Some("service" | "timer"))
+}
+
+#[async_trait]
+impl UnitProvider for CachedUnitProvider {
+    async fn system_state(&self, scope: UnitScope) -> Result<String, AppError> {
+        self.inner.system_state(scope).await
+    }
+
+    /// Serves service rows from synced snapshots, falling through per scope.
+    async fn list_service_units(&self, scope: UnitScope) -> Result<Vec<UnitStatus>, AppError> {
+        match scope {
+            UnitScope::System | UnitScope::User => self.service_units_for_single_scope(scope).await,
+            UnitScope::Both => combine_scope_rows_by_key(
+                self.service_units_for_single_scope(UnitScope::System).await,
+                self.service_units_for_single_scope(UnitScope::User).await,
+                "service units",
+                |unit| unit.unit.as_str(),
+            ),
+            UnitScope::UserId(_) | UnitScope::AllUsers | UnitScope::Machine(_) => {
+                self.inner.list_service_units(scope).await
+            }
+        }
+    }
+
+    async fn get_unit_status(
+        &self,
+        unit: &str,
+        scope: UnitScope,
+        transition_limit: usize,
+    ) -> Result<serde_json::Value, AppError> {
+        self.inner
+            .get_unit_status(unit, scope, transition_limit)
+            .await
+    }
+
+    /// Serves timer rows from synced snapshots, falling through per scope.
+    async fn list_timer_units(&self, scope: UnitScope) -> Result<Vec<TimerStatus>, AppError> {
+        match scope {
+            UnitScope::System | UnitScope::User => self.timer_units_for_single_scope(scope).await,
+            UnitScope::Both => combine_scope_rows_by_key(
+                self.timer_units_for_single_scope(UnitScope::System).await,
+                self.timer_units_for_single_scope(UnitScope::User).await,
+                "timer units",
+                |timer| timer.unit.as_str(),
+            ),
+            UnitScope::UserId(_) | UnitScope::AllUsers | UnitScope::Machine(_) => {
+                self.inner.list_timer_units(scope).await
+            }
+        }
+    }
+
+    async fn list_units(
+        &self,
+        scope: UnitScope,
+        unit_type: Option<&str>,
+    ) -> Result<Vec<UnitInfo>, AppError> {
+        self.inner.list_units(scope, unit_type).await
+    }
+
+    async fn unit_dependency_edges(
+        &self,
+        unit: &str,
+        scope: UnitScope,
+        direction: DependencyDirection,
+    ) -> Result<Vec<(String, String)>, AppError> {
+        self.inner
+            .unit_dependency_edges(unit, scope, direction)
+            .await
+    }
+
+    async fn get_unit_dependencies(
+        &self,
+        unit: &str,
+        scope: UnitScope,
+        direction: DependencyDirection,
+        max_depth: usize,
+        max_nodes: usize,
+    ) -> Result<UnitDependencyGraph, AppError> {
+        self.inner
+            .get_unit_dependencies(unit, scope, direction, max_depth, max_nodes)
+            .await
+    }
+
+    async fn unit_resources(
+        &self,
+        scope: UnitScope,
+        units: &[String],
+    ) -> Result<HashMap<String, UnitResources>, AppError> {
+        self.inner.unit_resources(scope, units).await
+    }
+
+    async fn sample_service_resources(
+        &self,
+        scope: UnitScope,
+    ) -> Result<Vec<ResourceSample>, AppError> {
+        self.inner.sample_service_resources(scope).await
+    }
+
+    async fn service_security_settings(
+        &self,
+        scope: UnitScope,
+        units: &[String],
+    ) -> Result<HashMap<String, ServiceSecuritySettings>, AppError> {
+        self.inner.service_security_settings(scope, units).await
+    }
+
+    async fn get_unit_file(&self, unit: &str, scope: UnitScope) -> Result<UnitFileView, AppError> {
+        self.inner.get_unit_file(unit, scope).await
+    }
+
+    async fn boot_timing(&self, scope: UnitScope) -> Result<BootTiming, AppError> {
+        self.inner.boot_timing(scope).await
+    }
+
+    async fn get_boot_timeline(
+        &self,
+        scope: UnitScope,
+        target: Option<&str>,
+        blame_limit: usize,
+    ) -> Result<BootTimeline, AppError> {
+        self.inner
+            .get_boot_timeline(scope, target, blame_limit)
+            .await
+    }
+
+    async fn list_journal_logs(&self, query: &LogQuery) -> Result<LogQueryResult, AppError> {
+        self.inner.list_journal_logs(query).await
+    }
+
+    async fn list_boots(&self, scope: UnitScope) -> Result<Vec<BootRecord>, AppError> {
+        self.inner.list_boots(scope).await
+    }
+
+    async fn list_login_sessions(&self) -> Result<LoginInventory, AppError> {
+        self.inner.list_login_sessions().await
+    }
+
+    async fn list_machines(&self, name: Option<&str>) -> Result<Vec<MachineInfo>, AppError> {
+        self.inner.list_machines(name).await
+    }
+
+    async fn list_jobs(&self, scope: UnitScope) -> Result<Vec<JobInfo>, AppError> {
+        self.inner.list_jobs(scope).await
+    }
+
+    async fn list_coredumps(
+        &self,
+        query: &LogQuery,
+        executable: Option<&str>,
+    ) -> Result<Vec<CoredumpInfo>, AppError> {
+        self.inner.list_coredumps(query, executable).await
+    }
+
+    async fn host_overview(&self) -> Result<HostOverview, AppError> {
+        self.inner.host_overview().await
+    }
+
+    async fn storage_status(&self) -> Result<StorageReport, AppError> {
+        self.inner.storage_status().await
+    }
+
+    async fn list_unit_runs(
+        &self,
+        unit: &str,
+        scope: UnitScope,
+        start_utc: DateTime<Utc>,
+        end_utc: DateTime<Utc>,
+    ) -> Result<UnitRunHistory, AppError> {
+        self.inner
+            .list_unit_runs(unit, scope, start_utc, end_utc)
+            .await
+    }
+
+    async fn refresh_units(
+        &self,
+        scope: UnitScope,
+        units: &[String],
+    ) -> Result<UnitRefresh, AppError> {
+        self.inner.refresh_units(scope, units).await
+    }
+
+    async fn connection_health(&self) -> Vec<ConnectionHealth> {
+        self.inner.connection_health().await
+    }
+
+    /// Reports the oldest synced snapshot among the requested scopes.
+    fn cache_age(&self, scope: UnitScope) -> Option<Duration> {
+        match scope {
+            UnitScope::System | UnitScope::User => self.age(scope),
+            UnitScope::Both => [UnitScope::System, UnitScope::User]
+                .into_iter()
+                .filter_map(|scope| self.age(scope))
+                .max(),
+            UnitScope::UserId(_) | UnitScope::AllUsers | UnitScope::Machine(_) => None,
+        }
+    }
+}
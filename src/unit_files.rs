@@ -0,0 +1,576 @@
+//! Read-only unit file and drop-in parsing with override provenance and redaction.
+
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    fs,
+    io::Read,
+    path::{Component, Path, PathBuf},
+};
+
+use crate::{mcp::server::is_sensitive_key, systemd_client::UnitScope};
+
+const MAX_UNIT_FILE_BYTES: u64 = 256 * 1024;
+const MAX_DROP_IN_FILES: usize = 64;
+
+/// Keys whose values are always replaced because they carry environment or credentials.
+const REDACTED_KEYS: [&str; 5] = [
+    "Environment",
+    "LoadCredential",
+    "LoadCredentialEncrypted",
+    "SetCredential",
+    "SetCredentialEncrypted",
+];
+
+/// Standard system manager unit directories, in systemd search order.
+const SYSTEM_UNIT_DIRECTORIES: [&str; 11] = [
+    "/etc/systemd/system.control",
+    "/run/systemd/system.control",
+    "/run/systemd/transient",
+    "/run/systemd/generator.early",
+    "/etc/systemd/system",
+    "/run/systemd/system",
+    "/run/systemd/generator",
+    "/usr/local/lib/systemd/system",
+    "/usr/lib/systemd/system",
+    "/lib/systemd/system",
+    "/run/systemd/generator.late",
+];
+
+/// Standard user manager unit directories that do not depend on the user's home.
+const USER_UNIT_DIRECTORIES: [&str; 5] = [
+    "/etc/systemd/user",
+    "/run/systemd/user",
+    "/usr/local/lib/systemd/user",
+    "/usr/lib/systemd/user",
+    "/lib/systemd/user",
+];
+
+/// Unit file locations reported by the manager for one loaded unit.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UnitFileSources {
+    pub fragment_path: Option<String>,
+    pub drop_in_paths: Vec<String>,
+    pub source_path: Option<String>,
+    pub need_daemon_reload: Option<bool>,
+}
+
+/// Parsed unit file view returned by `get_unit_file`.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct UnitFileView {
+    pub unit: String,
+    pub scope: String,
+    pub fragment_path: Option<String>,
+    pub drop_in_paths: Vec<String>,
+    pub source_path: Option<String>,
+    pub need_daemon_reload: Option<bool>,
+    pub sections: Vec<UnitFileSection>,
+    pub skipped_paths: Vec<SkippedUnitFile>,
+}
+
+/// One `[Section]` merged across the fragment and its drop-ins.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct UnitFileSection {
+    pub name: String,
+    pub entries: Vec<UnitFileEntry>,
+}
+
+/// One assignment with the file and line it came from.
+///
+/// `overridden_by` names the last later file that assigns the same key in the
+/// same section; list-valued keys such as `ExecStart=` may append rather than
+/// replace, so it marks provenance rather than guaranteed replacement.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct UnitFileEntry {
+    pub key: String,
+    pub value: String,
+    pub source: String,
+    pub line: usize,
+    pub redacted: bool,
+    pub overridden_by: Option<String>,
+}
+
+/// A reported unit file path that was not read, with a stable reason code.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct SkippedUnitFile {
+    pub path: String,
+    pub reason: String,
+}
+
+/// Reads the fragment and drop-ins named by `sources` and builds the parsed view.
+///
+/// Paths outside the standard unit directories for `scope`, unreadable files, and
+/// files over the size bound are skipped and reported instead of failing the
+/// request. This performs blocking file I/O and must run off the async runtime.
+pub fn load_unit_file_view(unit: &str, scope: UnitScope, sources: UnitFileSources) -> UnitFileView {
+    let mut files = Vec::new();
+    let mut skipped = Vec::new();
+    let mut skip = |path: &str, reason: &str| {
+        skipped.push(SkippedUnitFile {
+            path: path.to_string(),
+            reason: reason.to_string(),
+        });
+    };
+
+    let paths = sources
+        .fragment_path
+        .iter()
+        .chain(sources.drop_in_paths.iter().take(MAX_DROP_IN_FILES));
+    for path in paths {
+        let Some((root, relative)) = unit_file_location(Path::new(path), scope) else {
+            skip(path, "outside_unit_directories");
+            continue;
+        };
+        match read_unit_file_beneath(&root, &relative) {
+            Ok(Some(contents)) => files.push((path.clone(), contents)),
+            Ok(None) => skip(path, "too_large"),
+            Err(error) if error.raw_os_error() == Some(rustix::io::Errno::XDEV.raw_os_error()) => {
+                skip(path, "outside_unit_directories")
+            }
+            Err(_) => skip(path, "unreadable"),
+        }
+    }
+    for path in sources.drop_in_paths.iter().skip(MAX_DROP_IN_FILES) {
+        skip(path, "too_many_drop_ins");
+    }
+
+    build_unit_file_view(unit, scope, sources, &files, skipped)
+}
+
+/// Builds the merged, redacted view from already-read `(path, contents)` files.
+///
+/// Files must be ordered as systemd applies them: fragment first, then drop-ins.
+pub fn build_unit_file_view(
+    unit: &str,
+    scope: UnitScope,
+    sources: UnitFileSources,
+    files: &[(String, String)],
+    skipped_paths: Vec<SkippedUnitFile>,
+) -> UnitFileView {
+    let mut sections: Vec<UnitFileSection> = Vec::new();
+    let mut section_index: HashMap<String, usize> = HashMap::new();
+
+    for (path, contents) in files {
+        for (section, line, key, value) in parse_unit_file(contents) {
+            let index = *section_index.entry(section.clone()).or_insert_with(|| {
+                sections.push(UnitFileSection {
+                    name: section.clone(),
+                    entries: Vec::new(),
+                });
+                sections.len() - 1
+            });
+            let entries = &mut sections[index].entries;
+            for earlier in entries
+                .iter_mut()
+                .filter(|entry| entry.key == key && entry.source != *path)
+            {
+                earlier.overridden_by = Some(path.clone());
+            }
+            let (value, redacted) = redact_unit_value(&key, value);
+            entries.push(UnitFileEntry {
+                key,
+                value,
+                source: path.clone(),
+                line,
+                redacted,
+                overridden_by: None,
+            });
+        }
+    }
+
+    UnitFileView {
+        unit: unit.to_string(),
+        scope: scope.as_str().to_string(),
+        fragment_path: sources.fragment_path,
+        drop_in_paths: sources.drop_in_paths,
+        source_path: sources.source_path,
+        need_daemon_reload: sources.need_daemon_reload,
+        sections,
+        skipped_paths,
+    }
+}
+
+/// Parses unit file syntax into `(section, line, key, value)` assignments.
+///
+/// Supports `#`/`;` comments, `[Section]` headers, and trailing-backslash line
+/// continuation. Lines outside a section or without `=` are ignored. `line` is
+/// the 1-based line where the assignment starts.
+fn parse_unit_file(contents: &str) -> Vec<(String, usize, String, String)> {
+    let mut assignments = Vec::new();
+    let mut section: Option<String> = None;
+    let mut pending: Option<(usize, String)> = None;
+
+    for (index, raw_line) in contents.lines().enumerate() {
+        let line_number = index + 1;
+        let (start, mut logical) = match pending.take() {
+            Some((start, mut buffer)) => {
+                buffer.push(' ');
+                buffer.push_str(raw_line.trim());
+                (start, buffer)
+            }
+            None => {
+                let trimmed = raw_line.trim();
+                if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with(';') {
+                    continue;
+                }
+                (line_number, trimmed.to_string())
+            }
+        };
+        if logical.ends_with('\\') {
+            logical.pop();
+            pending = Some((start, logical.trim_end().to_string()));
+            continue;
+        }
+
+        if logical.starts_with('[') && logical.ends_with(']') {
+            section = Some(logical[1..logical.len() - 1].trim().to_string());
+            continue;
+        }
+        let (Some(section), Some((key, value))) = (section.as_ref(), logical.split_once('='))
+        else {
+            continue;
+        };
+        assignments.push((
+            section.clone(),
+            start,
+            key.trim().to_string(),
+            value.trim().to_string(),
+        ));
+    }
+
+    assignments
+}
+
+/// Redacts secret-bearing values and credential-like command arguments.
+///
+/// `Environment=` and credential keys are replaced wholesale. `Exec*=` command
+/// lines keep their shape, but `name=value` tokens with credential-like names and
+/// the token following a credential-like flag are replaced.
+fn redact_unit_value(key: &str, value: String) -> (String, bool) {
+    if REDACTED_KEYS.contains(&key) {
+        return ("[REDACTED]".to_string(), true);
+    }
+    if !key.starts_with("Exec") {
+        return (value, false);
+    }
+
+    let mut redacted = false;
+    let mut redact_next = false;
+    let tokens = value
+        .split_whitespace()
+        .map(|token| {
+            if redact_next {
+                redact_next = false;
+                redacted = true;
+                return "[REDACTED]".to_string();
+            }
+            if let Some((name, _)) = token.split_once('=')
+                && is_sensitive_key(name.trim_start_matches('-'))
+            {
+                redacted = true;
+                return format!("{name}=[REDACTED]");
+            }
+            if token.starts_with('-') && is_sensitive_key(token.trim_start_matches('-')) {
+                redact_next = true;
+            }
+            token.to_string()
+        })
+        .collect::<Vec<_>>();
+
+    if redacted {
+        (tokens.join(" "), true)
+    } else {
+        (value, false)
+    }
+}
+
+/// Splits `path` into the standard unit directory for `scope` containing it and
+/// the remainder relative to that directory.
+///
+/// The path must be absolute and free of `..` components. This check is lexical
+/// only; symlinks are confined when the file is opened beneath the directory.
+fn unit_file_location(path: &Path, scope: UnitScope) -> Option<(PathBuf, PathBuf)> {
+    if !path.is_absolute()
+        || path
+            .components()
+            .any(|component| matches!(component, Component::ParentDir))
+    {
+        return None;
+    }
+    unit_directories(scope).into_iter().find_map(|root| {
+        let relative = path.strip_prefix(&root).ok()?.to_path_buf();
+        (!relative.as_os_str().is_empty()).then_some((root, relative))
+    })
+}
+
+/// Lists the standard unit directories searched by the manager for `scope`.
+fn unit_directories(scope: UnitScope) -> Vec<PathBuf> {
+    match scope {
+        UnitScope::User => {
+            let mut roots = USER_UNIT_DIRECTORIES
+                .iter()
+                .map(PathBuf::from)
+                .collect::<Vec<_>>();
+            if let Some(home) = std::env::var_os("HOME") {
+                let home = PathBuf::from(home);
+                roots.push(home.join(".config/systemd/user"));
+                roots.push(home.join(".local/share/systemd/user"));
+            }
+            if let Some(runtime) = std::env::var_os("XDG_RUNTIME_DIR") {
+                roots.push(PathBuf::from(runtime).join("systemd"));
+            }
+            roots
+        }
+        UnitScope::UserId(uid) => {
+            let mut roots = USER_UNIT_DIRECTORIES
+                .iter()
+                .map(PathBuf::from)
+                .collect::<Vec<_>>();
+            if let Some(home) = fs::read_to_string("/etc/passwd")
+                .ok()
+                .and_then(|passwd| passwd_home(&passwd, uid))
+            {
+                roots.push(home.join(".config/systemd/user"));
+                roots.push(home.join(".local/share/systemd/user"));
+            }
+            roots.push(PathBuf::from(format!("/run/user/{uid}/systemd")));
+            roots
+        }
+        UnitScope::System | UnitScope::Both | UnitScope::AllUsers => {
+            SYSTEM_UNIT_DIRECTORIES.iter().map(PathBuf::from).collect()
+        }
+        // Fragment paths of a machine name files inside the container; reading
+        // them on the host would return the host's files, so none are allowed.
+        UnitScope::Machine(_) => Vec::new(),
+    }
+}
+
+/// Finds the absolute home directory of `uid` in `passwd`-formatted text.
+fn passwd_home(passwd: &str, uid: u32) -> Option<PathBuf> {
+    passwd.lines().find_map(|line| {
+        let fields = line.split(':').collect::<Vec<_>>();
+        let home = fields.get(5)?;
+        (fields.get(2)?.parse::<u32>().ok()? == uid && home.starts_with('/'))
+            .then(|| PathBuf::from(home))
+    })
+}
+
+/// Reads a UTF-8 file up to `MAX_UNIT_FILE_BYTES` from `relative` beneath `root`;
+/// `Ok(None)` means it was too large.
+///
+/// The root is opened once and the file is resolved relative to it with
+/// `RESOLVE_BENEATH | RESOLVE_NO_MAGICLINKS`, so a symlink leaving the directory,
+/// including one swapped in concurrently, fails with `EXDEV` or `ELOOP` instead
+/// of being followed. Anything other than a regular file is refused.
+fn read_unit_file_beneath(root: &Path, relative: &Path) -> std::io::Result<Option<String>> {
+    use rustix::fs::{FileType, Mode, OFlags, ResolveFlags};
+
+    let root = rustix::fs::open(
+        root,
+        OFlags::PATH | OFlags::DIRECTORY | OFlags::CLOEXEC,
+        Mode::empty(),
+    )?;
+    let file = rustix::fs::openat2(
+        &root,
+        relative,
+        OFlags::RDONLY | OFlags::NONBLOCK | OFlags::NOCTTY | OFlags::CLOEXEC,
+        Mode::empty(),
+        ResolveFlags::BENEATH | ResolveFlags::NO_MAGICLINKS,
+    )?;
+    if FileType::from_raw_mode(rustix::fs::fstat(&file)?.st_mode) != FileType::RegularFile {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "unit file is not a regular file",
+        ));
+    }
+    let mut contents = String::new();
+    fs::File::from(file)
+        .take(MAX_UNIT_FILE_BYTES + 1)
+        .read_to_string(&mut contents)?;
+    if contents.len() as u64 > MAX_UNIT_FILE_BYTES {
+        return Ok(None);
+    }
+    Ok(Some(contents))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        UnitFileSources, build_unit_file_view, parse_unit_file, passwd_home,
+        read_unit_file_beneath, redact_unit_value, unit_file_location,
+    };
+    use crate::systemd_client::UnitScope;
+    use std::path::Path;
+
+    #[test]
+    fn parses_sections_comments_and_continuations() {
+        let parsed = parse_unit_file(
+            "# comment\n[Unit]\nDescription=Demo\n; other\n[Service]\nExecStart=/usr/bin/demo \\\n  --flag\nnot an assignment\n",
+        );
+
+        assert_eq!(
+            parsed,
+            vec![
+                (
+                    "Unit".to_string(),
+                    3,
+                    "Description".to_string(),
+                    "Demo".to_string()
+                ),
+                (
+                    "Service".to_string(),
+                    6,
+                    "ExecStart".to_string(),
+                    "/usr/bin/demo --flag".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn redacts_environment_credentials_and_sensitive_exec_arguments() {
+        assert_eq!(
+            redact_unit_value("Environment", "API_KEY=abc".to_string()),
+            ("[REDACTED]".to_string(), true)
+        );
+        assert_eq!(
+            redact_unit_value("SetCredential", "db:secret".to_string()),
+            ("[REDACTED]".to_string(), true)
+        );
+        assert_eq!(
+            redact_unit_value(
+                "ExecStart",
+                "/usr/bin/app --token abc --password=hunter2 --port 80".to_string()
+            ),
+            (
+                "/usr/bin/app --token [REDACTED] --password=[REDACTED] --port 80".to_string(),
+                true
+            )
+        );
+        assert_eq!(
+            redact_unit_value("User", "app".to_string()),
+            ("app".to_string(), false)
+        );
+    }
+
+    #[test]
+    fn marks_drop_in_override_provenance() {
+        let view = build_unit_file_view(
+            "demo.service",
+            UnitScope::System,
+            UnitFileSources::default(),
+            &[
+                (
+                    "/usr/lib/systemd/system/demo.service".to_string(),
+                    "[Service]\nRestart=no\nUser=app\n".to_string(),
+                ),
+                (
+                    "/etc/systemd/system/demo.service.d/override.conf".to_string(),
+                    "[Service]\nRestart=always\n".to_string(),
+                ),
+            ],
+            Vec::new(),
+        );
+
+        let entries = &view.sections[0].entries;
+        assert_eq!(entries.len(), 3);
+        assert_eq!(
+            entries[0].overridden_by.as_deref(),
+            Some("/etc/systemd/system/demo.service.d/override.conf")
+        );
+        assert_eq!(entries[1].overridden_by, None);
+        assert_eq!(entries[2].value, "always");
+    }
+
+    #[test]
+    fn refuses_paths_outside_standard_unit_directories() {
+        assert!(
+            unit_file_location(
+                Path::new("/etc/systemd/system/demo.service"),
+                UnitScope::System
+            )
+            .is_some()
+        );
+        assert!(!unit_file_location(Path::new("/etc/shadow"), UnitScope::System).is_some());
+        assert!(
+            !unit_file_location(
+                Path::new("/etc/systemd/system/../../shadow"),
+                UnitScope::System
+            )
+            .is_some()
+        );
+        assert!(
+            !unit_file_location(Path::new("relative/demo.service"), UnitScope::System).is_some()
+        );
+        assert!(
+            !unit_file_location(
+                Path::new("/etc/systemd/user/demo.service"),
+                UnitScope::System
+            )
+            .is_some()
+        );
+    }
+
+    #[test]
+    fn user_id_scope_allows_that_users_unit_directories_only() {
+        assert!(
+            unit_file_location(
+                Path::new("/run/user/1000/systemd/transient/demo.service"),
+                UnitScope::UserId(1000)
+            )
+            .is_some()
+        );
+        assert!(
+            !unit_file_location(
+                Path::new("/run/user/1001/systemd/transient/demo.service"),
+                UnitScope::UserId(1000)
+            )
+            .is_some()
+        );
+        assert!(
+            !unit_file_location(
+                Path::new("/etc/systemd/system/demo.service"),
+                UnitScope::UserId(1000)
+            )
+            .is_some()
+        );
+
+        let passwd = "root:x:0:0:root:/root:/bin/bash\nalice:x:1000:1000::/home/alice:/bin/sh\nodd:x:1001:1001::relative:/bin/sh\n";
+        assert_eq!(
+            passwd_home(passwd, 1000),
+            Some(Path::new("/home/alice").to_path_buf())
+        );
+        assert_eq!(passwd_home(passwd, 1001), None);
+        assert_eq!(passwd_home(passwd, 1002), None);
+    }
+
+    #[test]
+    fn unit_file_reads_refuse_symlinks_leaving_the_directory() {
+        let base = std::env::temp_dir().join(format!("unit-files-{}", std::process::id()));
+        let root = base.join("system");
+        std::fs::create_dir_all(&root).expect("root dir");
+        std::fs::write(base.join("secret"), "secret").expect("outside file");
+        std::fs::write(root.join("demo.service"), "[Unit]\n").expect("unit file");
+        std::os::unix::fs::symlink("demo.service", root.join("alias.service")).expect("symlink");
+
+        let read = |relative: &str| read_unit_file_beneath(&root, Path::new(relative));
+        assert_eq!(
+            read("demo.service").expect("unit"),
+            Some("[Unit]\n".to_string())
+        );
+        assert_eq!(
+            read("alias.service").expect("alias"),
+            Some("[Unit]\n".to_string())
+        );
+
+        std::os::unix::fs::symlink(base.join("secret"), root.join("absolute.service"))
+            .expect("symlink");
+        assert!(read("absolute.service").is_err());
+        std::os::unix::fs::symlink("../secret", root.join("dotdot.service")).expect("symlink");
+        assert!(read("dotdot.service").is_err());
+        std::fs::create_dir(root.join("dir.service")).expect("dir");
+        assert!(read("dir.service").is_err());
+
+        std::fs::remove_dir_all(&base).expect("cleanup");
+    }
+}